@@ -38,29 +38,31 @@ fn move_santa(location: IPoint2D, direction: CardinalDirection) -> IPoint2D {
     }
 }
 
-fn part1(directions: &[CardinalDirection]) -> Result<usize> {
-    let mut visited = HashSet::new();
-    let mut location = IPoint2D::ORIGIN;
-    visited.insert(location);
-    for direction in directions {
-        location = move_santa(location, *direction);
-        visited.insert(location);
+// santas take turns following the directions, all starting at the origin. with no santas nobody
+// moves, so only the origin gets a present
+fn houses_visited(directions: &[CardinalDirection], santas: usize) -> usize {
+    if santas == 0 {
+        return 1;
     }
 
-    Ok(visited.len())
-}
-
-fn part2(directions: &[CardinalDirection]) -> Result<usize> {
     let mut visited = HashSet::new();
-    let mut locations = [IPoint2D::ORIGIN, IPoint2D::ORIGIN];
-    visited.insert(locations[0]);
+    let mut locations = vec![IPoint2D::ORIGIN; santas];
+    visited.insert(IPoint2D::ORIGIN);
     for (pos, direction) in directions.iter().enumerate() {
-        let ix = pos % 2;
+        let ix = pos % santas;
         locations[ix] = move_santa(locations[ix], *direction);
         visited.insert(locations[ix]);
     }
 
-    Ok(visited.len())
+    visited.len()
+}
+
+fn part1(directions: &[CardinalDirection]) -> Result<usize> {
+    Ok(houses_visited(directions, 1))
+}
+
+fn part2(directions: &[CardinalDirection]) -> Result<usize> {
+    Ok(houses_visited(directions, 2))
 }
 
 #[cfg(test)]
@@ -84,4 +86,13 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn three_santas() -> Result<()> {
+        assert_eq!(houses_visited(&parse_chars("^>v<^>")?, 3), 7);
+        assert_eq!(houses_visited(&parse_chars("^v^v^v")?, 3), 3);
+        assert_eq!(houses_visited(&parse_chars("^>v<^>")?, 0), 1);
+
+        Ok(())
+    }
 }
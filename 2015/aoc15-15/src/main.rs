@@ -68,42 +68,27 @@ where
     }
 }
 
-fn two_ingredient_permutations() -> Vec<Vec<i32>> {
-    let mut result = vec![];
-    for x in 1..=99 {
-        result.push(vec![x, 100 - x]);
-    }
-    result
-}
-
-// returns all the permutations for a given set of ingredients
-// ingredients must add to 100 tsps and each ingredient must have at least 1 tsp
-fn four_ingredient_permutations() -> Vec<Vec<i32>> {
-    let mut result = vec![];
-    for x in 1..=97 {
-        for y in 1..=(98 - x) {
-            for z in 1..=(99 - x - y) {
-                result.push(vec![x, y, z, 100 - x - y - z]);
-            }
-        }
-    }
-    result
+// yields every way to split total tsps across the given number of ingredients, with at least
+// 1 tsp for each ingredient
+fn compositions(total: i32, parts: usize) -> impl Iterator<Item = Vec<i32>> {
+    let iter: Box<dyn Iterator<Item = Vec<i32>>> = match parts {
+        0 => Box::new(std::iter::empty()),
+        1 => Box::new((total >= 1).then(|| vec![total]).into_iter()),
+        _ => Box::new((1..=total - (parts as i32 - 1)).flat_map(move |first| {
+            compositions(total - first, parts - 1).map(move |mut rest| {
+                rest.insert(0, first);
+                rest
+            })
+        })),
+    };
+    iter
 }
 
 fn get_best_cookie(ingredients: &[Ingredient], set_calories: bool) -> i32 {
-    let permutations = match ingredients.len() {
-        2 => two_ingredient_permutations(),
-        4 => four_ingredient_permutations(),
-        _ => panic!(),
-    };
-    let mut max_score = 0;
-    for p in permutations {
-        max_score = max(
-            total_score(p.iter().copied().zip(ingredients), set_calories),
-            max_score,
-        );
-    }
-    max_score
+    compositions(100, ingredients.len())
+        .map(|tsps| total_score(tsps.into_iter().zip(ingredients), set_calories))
+        .max()
+        .unwrap_or(0)
 }
 
 fn part1(ingredients: &[Ingredient]) -> Result<i32> {
@@ -146,6 +131,37 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn compositions_count() {
+        assert_eq!(
+            compositions(5, 3).collect::<Vec<_>>(),
+            vec![
+                vec![1, 1, 3],
+                vec![1, 2, 2],
+                vec![1, 3, 1],
+                vec![2, 1, 2],
+                vec![2, 2, 1],
+                vec![3, 1, 1],
+            ]
+        );
+        assert_eq!(compositions(100, 2).count(), 99);
+        // 99 choose 3
+        assert_eq!(compositions(100, 4).count(), 156849);
+        assert_eq!(compositions(2, 3).count(), 0);
+    }
+
+    #[test]
+    fn sample_four_ingredients() -> Result<()> {
+        // splitting each sample ingredient in two can still reach the best 2 ingredient mix
+        let mut ingredients = parse_lines(SAMPLE)?;
+        ingredients.extend(parse_lines::<Ingredient>(SAMPLE)?);
+
+        assert_eq!(part1(&ingredients)?, 62842880);
+        assert_eq!(part2(&ingredients)?, 57600000);
+
+        Ok(())
+    }
+
     const SAMPLE: &str = "\
 Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8
 Cinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3
@@ -43,22 +43,17 @@ impl FromStr for Problem {
 impl Problem {
     fn run(&self, steps: usize, stuck_on: &[Point2D]) -> Grid2D<Light> {
         let mut lights = self.lights.clone();
-        for stuck in stuck_on {
-            lights[*stuck] = On;
-        }
         for _ in 0..steps {
-            lights = lights.map(|(pt, l)| {
-                if stuck_on.contains(&pt) {
-                    On
-                } else {
-                    let neighbors_on = lights.neighbors(pt).filter(|(_, l)| l == &&On).count();
-                    match (l, neighbors_on) {
-                        (Off, 3) => On,
-                        (On, 2..=3) => On,
-                        _ => Off,
-                    }
-                }
-            });
+            lights = lights.life_step_with_fixed(
+                |l| l == &On,
+                |l, neighbors_on| match (l, neighbors_on) {
+                    (Off, 3) => On,
+                    (On, 2..=3) => On,
+                    _ => Off,
+                },
+                stuck_on,
+                On,
+            );
         }
         lights
     }
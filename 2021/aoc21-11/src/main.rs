@@ -39,28 +39,23 @@ fn step(grid: &mut Grid2D<u32>) -> usize {
     flashes
 }
 
-fn part1(grid: &Grid2D<u32>) -> Result<usize> {
-    let mut grid = grid.clone();
-    let mut flashes = 0;
-    for _ in 0..100 {
-        flashes += step(&mut grid);
-    }
+// lazily yields the number of flashes on each successive step
+fn flash_steps(grid: Grid2D<u32>) -> impl Iterator<Item = usize> {
+    let mut grid = grid;
+    std::iter::from_fn(move || Some(step(&mut grid)))
+}
 
-    Ok(flashes)
+fn part1(grid: &Grid2D<u32>) -> Result<usize> {
+    Ok(flash_steps(grid.clone()).take(100).sum())
 }
 
-fn part2(contents: &Grid2D<u32>) -> Result<usize> {
-    let mut grid = contents.clone();
-    let mut steps = 1;
-    loop {
-        step(&mut grid);
-        if grid.iter_horizontal().all(|(_, x)| x == &0) {
-            break;
-        }
-        steps += 1;
-    }
+fn part2(grid: &Grid2D<u32>) -> Result<usize> {
+    let total = grid.bounds.len();
+    let steps = flash_steps(grid.clone())
+        .position(|flashes| flashes == total)
+        .unwrap();
 
-    Ok(steps)
+    Ok(steps + 1)
 }
 
 #[cfg(test)]
@@ -89,6 +84,17 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn sample_flash_steps() -> Result<()> {
+        let parsed = Grid2D::<u32>::from_char_str(SAMPLE)?;
+
+        let flashes: Vec<usize> = flash_steps(parsed).take(10).collect();
+
+        assert_eq!(flashes, vec![0, 35, 45, 16, 8, 1, 7, 24, 39, 29]);
+
+        Ok(())
+    }
+
     const SAMPLE: &str = "\
 5483143223
 2745854711
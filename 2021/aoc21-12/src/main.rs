@@ -1,5 +1,7 @@
 use anyhow::*;
 use aoc_common::run;
+#[cfg(test)]
+use std::cell::Cell;
 use std::collections::HashMap;
 
 fn main() -> Result<()> {
@@ -8,19 +10,10 @@ fn main() -> Result<()> {
 
 struct Map {
     nodes: HashMap<String, Node>,
-}
-
-fn calc_max_small_cave_visits(path_so_far: &[&str]) -> usize {
-    let len = path_so_far.len();
-    for index in 0..len {
-        let node = path_so_far[index];
-        if node.chars().next().unwrap().is_lowercase()
-            && path_so_far[index + 1..len].iter().any(|x| *x == node)
-        {
-            return 1;
-        }
-    }
-    2
+    // how many times count_paths_from/count_paths_memo_from have been entered, so the two
+    // approaches can be compared without timing them
+    #[cfg(test)]
+    calls: Cell<usize>,
 }
 
 impl Map {
@@ -38,47 +31,108 @@ impl Map {
         }
     }
 
-    fn traverse<'a>(&'a self, at: String, path_so_far: Vec<&'a str>) -> Vec<Vec<&'a str>> {
-        let mut paths = vec![];
-        for next in &self.nodes[&at].edges {
-            let next_node = &self.nodes[next];
-            if next_node.is_small && path_so_far.iter().any(|x| *x == next) {
+    // counts paths from start to end without building them. small caves can be visited once,
+    // or if allow_double is set, a single small cave may be visited twice
+    fn count_paths(&self, allow_double: bool) -> usize {
+        let mut visits = HashMap::new();
+        visits.insert("start", 1);
+        self.count_paths_from("start", &mut visits, allow_double)
+    }
+
+    fn count_paths_from<'a>(
+        &'a self,
+        at: &str,
+        visits: &mut HashMap<&'a str, usize>,
+        allow_double: bool,
+    ) -> usize {
+        #[cfg(test)]
+        self.calls.set(self.calls.get() + 1);
+        let mut count = 0;
+        for next in &self.nodes[at].edges {
+            if next == "end" {
+                count += 1;
                 continue;
             }
-            let mut path = path_so_far.clone();
-            path.push(next);
-            if next == "end" {
-                paths.push(path);
-            } else {
-                for other_path in self.traverse(next.to_string(), path) {
-                    paths.push(other_path);
+
+            let next_node = &self.nodes[next];
+            let mut uses_double = false;
+            if next_node.is_small && visits.get(next.as_str()).copied().unwrap_or(0) > 0 {
+                if !allow_double {
+                    continue;
                 }
+                uses_double = true;
+            }
+
+            if next_node.is_small {
+                *visits.entry(next).or_insert(0) += 1;
+            }
+            count += self.count_paths_from(next, visits, allow_double && !uses_double);
+            if next_node.is_small {
+                *visits.get_mut(next.as_str()).unwrap() -= 1;
             }
         }
-        paths
+        count
     }
 
-    fn traverse2<'a>(&'a self, at: String, path_so_far: Vec<&'a str>) -> Vec<Vec<&'a str>> {
-        let max_small_cave_visits = calc_max_small_cave_visits(&path_so_far);
-        let mut paths = vec![];
-        for next in &self.nodes[&at].edges {
-            let next_node = &self.nodes[next];
-            if next_node.is_small
-                && path_so_far.iter().filter(|x| x == &next).count() >= max_small_cave_visits
-            {
+    // same as count_paths, but caches the count for each (cave, visited small caves, double
+    // still available) state. big caves can be revisited freely so they aren't part of the key
+    fn count_paths_memo(&self, allow_double: bool) -> usize {
+        let small_caves: HashMap<&str, u64> = self
+            .nodes
+            .iter()
+            .filter(|(_, node)| node.is_small)
+            .enumerate()
+            .map(|(ix, (name, _))| (name.as_str(), 1 << ix))
+            .collect();
+        let mut cache = HashMap::new();
+        self.count_paths_memo_from(
+            "start",
+            small_caves["start"],
+            allow_double,
+            &small_caves,
+            &mut cache,
+        )
+    }
+
+    fn count_paths_memo_from<'a>(
+        &'a self,
+        at: &'a str,
+        visited: u64,
+        allow_double: bool,
+        small_caves: &HashMap<&str, u64>,
+        cache: &mut HashMap<(&'a str, u64, bool), usize>,
+    ) -> usize {
+        #[cfg(test)]
+        self.calls.set(self.calls.get() + 1);
+        if let Some(count) = cache.get(&(at, visited, allow_double)) {
+            return *count;
+        }
+
+        let mut count = 0;
+        for next in &self.nodes[at].edges {
+            if next == "end" {
+                count += 1;
                 continue;
             }
-            let mut path = path_so_far.clone();
-            path.push(next);
-            if next == "end" {
-                paths.push(path);
-            } else {
-                for other_path in self.traverse2(next.to_string(), path) {
-                    paths.push(other_path);
+
+            let mask = small_caves.get(next.as_str()).copied().unwrap_or(0);
+            if visited & mask != 0 {
+                if allow_double {
+                    count += self.count_paths_memo_from(next, visited, false, small_caves, cache);
                 }
+            } else {
+                count += self.count_paths_memo_from(
+                    next,
+                    visited | mask,
+                    allow_double,
+                    small_caves,
+                    cache,
+                );
             }
         }
-        paths
+
+        cache.insert((at, visited, allow_double), count);
+        count
     }
 }
 
@@ -90,6 +144,8 @@ struct Node {
 fn parse(contents: &str) -> Result<Map> {
     let mut map = Map {
         nodes: HashMap::new(),
+        #[cfg(test)]
+        calls: Cell::new(0),
     };
     for line in contents.lines() {
         let mut edge = line.split('-');
@@ -106,14 +162,11 @@ fn parse(contents: &str) -> Result<Map> {
 }
 
 fn part1(map: &Map) -> Result<usize> {
-    let paths = map.traverse("start".to_string(), vec!["start"]);
-
-    Ok(paths.len())
+    Ok(map.count_paths(false))
 }
 
 fn part2(map: &Map) -> Result<usize> {
-    let paths = map.traverse2("start".to_string(), vec!["start"]);
-    Ok(paths.len())
+    Ok(map.count_paths_memo(true))
 }
 
 #[cfg(test)]
@@ -186,6 +239,62 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn count_paths_samples() -> Result<()> {
+        for (sample, single, double) in [
+            (SAMPLE, 10, 36),
+            (SAMPLE_MED, 19, 103),
+            (SAMPLE_LRG, 226, 3509),
+        ] {
+            let map = parse(sample)?;
+            assert_eq!(map.count_paths(false), single);
+            assert_eq!(map.count_paths(true), double);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn count_paths_memo_samples() -> Result<()> {
+        for sample in [SAMPLE, SAMPLE_MED, SAMPLE_LRG] {
+            let map = parse(sample)?;
+            assert_eq!(map.count_paths_memo(false), map.count_paths(false));
+            assert_eq!(map.count_paths_memo(true), map.count_paths(true));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn count_paths_memo_dense() -> Result<()> {
+        // every small cave is connected to every other and to one big hub
+        let small = ["a", "b", "c", "d", "e"];
+        let mut input = "start-HUB\nHUB-end\nstart-a\ne-end\n".to_string();
+        for (ix, cave) in small.iter().enumerate() {
+            input += &format!("HUB-{cave}\n");
+            for other in &small[ix + 1..] {
+                input += &format!("{cave}-{other}\n");
+            }
+        }
+        let map = parse(&input)?;
+
+        map.calls.set(0);
+        let naive = map.count_paths(true);
+        let naive_calls = map.calls.get();
+
+        map.calls.set(0);
+        let memo = map.count_paths_memo(true);
+        let memo_calls = map.calls.get();
+
+        assert_eq!(memo, naive);
+        assert!(
+            memo_calls < naive_calls,
+            "memoized made {memo_calls} calls vs naive {naive_calls}"
+        );
+
+        Ok(())
+    }
+
     const SAMPLE: &str = "\
 start-A
 start-b
@@ -224,6 +224,157 @@ impl fmt::Debug for FishNum {
     }
 }
 
+// same snailfish number as FishNum but stored as a binary tree so explode and split don't
+// have to shift the whole token list around
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FishNumTree {
+    Leaf(u32),
+    Pair(Box<FishNumTree>, Box<FishNumTree>),
+}
+
+impl FishNumTree {
+    fn pair(left: FishNumTree, right: FishNumTree) -> FishNumTree {
+        FishNumTree::Pair(Box::new(left), Box::new(right))
+    }
+
+    fn from_tokens<I>(tokens: &mut I) -> Result<FishNumTree>
+    where
+        I: Iterator<Item = Token>,
+    {
+        match tokens.next() {
+            Some(Token::Number(num)) => Ok(FishNumTree::Leaf(num)),
+            Some(Token::Open) => {
+                let left = FishNumTree::from_tokens(tokens)?;
+                ensure!(tokens.next() == Some(Token::Separator), "expected ','");
+                let right = FishNumTree::from_tokens(tokens)?;
+                ensure!(tokens.next() == Some(Token::Close), "expected ']'");
+                Ok(FishNumTree::pair(left, right))
+            }
+            other => bail!("unexpected token {:?}", other),
+        }
+    }
+
+    fn plus(&self, other: &FishNumTree) -> FishNumTree {
+        let mut result = FishNumTree::pair(self.clone(), other.clone());
+
+        result.reduce();
+
+        result
+    }
+
+    fn reduce(&mut self) {
+        while self.explode(0).is_some() || self.split() {}
+    }
+
+    // explodes the leftmost pair nested inside 4 others, returning the left and right values
+    // that still need to be added to a neighbor
+    fn explode(&mut self, depth: usize) -> Option<(Option<u32>, Option<u32>)> {
+        let FishNumTree::Pair(left, right) = self else {
+            return None;
+        };
+
+        if depth == 4 {
+            let (FishNumTree::Leaf(l), FishNumTree::Leaf(r)) = (left.as_ref(), right.as_ref())
+            else {
+                panic!("cannot reduce too deeply nested item");
+            };
+            let carry = (Some(*l), Some(*r));
+            *self = FishNumTree::Leaf(0);
+            return Some(carry);
+        }
+
+        if let Some((carry_left, carry_right)) = left.explode(depth + 1) {
+            if let Some(num) = carry_right {
+                right.add_leftmost(num);
+            }
+            return Some((carry_left, None));
+        }
+
+        if let Some((carry_left, carry_right)) = right.explode(depth + 1) {
+            if let Some(num) = carry_left {
+                left.add_rightmost(num);
+            }
+            return Some((None, carry_right));
+        }
+
+        None
+    }
+
+    fn add_leftmost(&mut self, num: u32) {
+        match self {
+            FishNumTree::Leaf(x) => *x += num,
+            FishNumTree::Pair(left, _) => left.add_leftmost(num),
+        }
+    }
+
+    fn add_rightmost(&mut self, num: u32) {
+        match self {
+            FishNumTree::Leaf(x) => *x += num,
+            FishNumTree::Pair(_, right) => right.add_rightmost(num),
+        }
+    }
+
+    // splits the leftmost number greater than 9
+    fn split(&mut self) -> bool {
+        match self {
+            FishNumTree::Leaf(num) if *num > 9 => {
+                let left = *num / 2;
+                *self = FishNumTree::pair(FishNumTree::Leaf(left), FishNumTree::Leaf(*num - left));
+                true
+            }
+            FishNumTree::Leaf(_) => false,
+            FishNumTree::Pair(left, right) => left.split() || right.split(),
+        }
+    }
+
+    fn magnitude(&self) -> u32 {
+        match self {
+            FishNumTree::Leaf(num) => *num,
+            FishNumTree::Pair(left, right) => 3 * left.magnitude() + 2 * right.magnitude(),
+        }
+    }
+}
+
+impl Add for FishNumTree {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let mut result = FishNumTree::pair(self, other);
+
+        result.reduce();
+
+        result
+    }
+}
+
+impl From<&FishNum> for FishNumTree {
+    fn from(num: &FishNum) -> Self {
+        FishNumTree::from_tokens(&mut num.contents.iter().copied())
+            .expect("FishNum should contain a well formed number")
+    }
+}
+
+impl FromStr for FishNumTree {
+    type Err = Error;
+
+    fn from_str(num: &str) -> Result<Self> {
+        let num: FishNum = num.parse()?;
+        let mut tokens = num.contents.into_iter();
+        let tree = FishNumTree::from_tokens(&mut tokens)?;
+        ensure!(tokens.next().is_none(), "trailing tokens after number");
+        Ok(tree)
+    }
+}
+
+impl fmt::Display for FishNumTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FishNumTree::Leaf(num) => write!(f, "{}", num),
+            FishNumTree::Pair(left, right) => write!(f, "[{},{}]", left, right),
+        }
+    }
+}
+
 impl Token {
     fn from_char(c: char) -> Option<Token> {
         match c {
@@ -258,6 +409,7 @@ fn part1(contents: &[FishNum]) -> Result<u32> {
 }
 
 fn part2(contents: &[FishNum]) -> Result<u32> {
+    let contents: Vec<FishNumTree> = contents.iter().map(FishNumTree::from).collect();
     contents
         .iter()
         .map(|x| -> Result<u32> {
@@ -394,6 +546,62 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn tree_explode_matches_vec() -> Result<()> {
+        for num in [
+            "[[[[[9,8],1],2],3],4]",
+            "[7,[6,[5,[4,[3,2]]]]]",
+            "[[6,[5,[4,[3,2]]]],1]",
+            "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]",
+        ] {
+            let mut vec: FishNum = num.parse()?;
+            let mut tree: FishNumTree = num.parse()?;
+            vec.reduce();
+            tree.reduce();
+            assert_eq!(tree.to_string(), vec.to_string());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn tree_sum_matches_vec() -> Result<()> {
+        for sample in [SIMPLE_SUM, HARDER_SUM, SAMPLE] {
+            let vec: FishNum = parse(sample)?.into_iter().sum();
+            let tree = sample
+                .lines()
+                .map(|line| line.parse::<FishNumTree>())
+                .collect::<Result<Vec<_>>>()?
+                .into_iter()
+                .reduce(|acc, x| acc + x)
+                .unwrap();
+
+            assert_eq!(tree.to_string(), vec.to_string());
+            assert_eq!(tree.magnitude(), vec.magnitude());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn tree_pairwise_max_matches_vec() -> Result<()> {
+        let nums = parse(SAMPLE)?;
+        let trees: Vec<FishNumTree> = nums.iter().map(FishNumTree::from).collect();
+
+        let vec_max = nums
+            .iter()
+            .flat_map(|x| nums.iter().map(move |y| x.plus(y).magnitude()))
+            .max();
+        let tree_max = trees
+            .iter()
+            .flat_map(|x| trees.iter().map(move |y| x.plus(y).magnitude()))
+            .max();
+
+        assert_eq!(tree_max, vec_max);
+
+        Ok(())
+    }
+
     const SIMPLE_SUM: &str = "\
 [1,1]
 [2,2]
@@ -1,4 +1,3 @@
-use std::collections::VecDeque;
 use std::str::FromStr;
 
 use anyhow::*;
@@ -22,41 +21,8 @@ impl FromStr for Problem {
     }
 }
 
-fn index_of(buffer: &VecDeque<char>, c: &char) -> Option<usize> {
-    for (ix, item) in buffer.iter().enumerate() {
-        if item == c {
-            return Some(ix);
-        }
-    }
-
-    None
-}
-
-fn find_pos(contents: &str, marker_len: usize) -> Result<usize> {
-    let mut buffer: VecDeque<char> = VecDeque::new();
-
-    for (pos, c) in contents.chars().enumerate() {
-        if let Some(ix) = index_of(&buffer, &c) {
-            // this rotates characters moving the last occurrence of the repeated character to
-            // the end of the ring buffer, then removes them
-            //
-            // ex: buffer=abcdef with current character d (ix=3)
-            buffer.rotate_left(ix + 1);
-            // buffer=efabcd
-            buffer.resize(buffer.len() - ix - 1, ' ');
-            // buffer=ef
-        }
-        buffer.push_back(c);
-        if buffer.len() == marker_len {
-            return Ok(pos + 1);
-        }
-    }
-
-    bail!("no position found");
-}
-
 // alternate solution using .windows()
-// same runtime as find_pos
+// same runtime as first_window_all_unique
 fn find_pos_windows(contents: &str, marker_len: usize) -> Result<usize> {
     for (pos, window) in contents
         .chars()
@@ -81,7 +47,7 @@ impl Solution for Problem {
     type Part2 = usize;
 
     fn part1(&mut self) -> Result<usize> {
-        find_pos(self.input.as_str(), 4)
+        first_window_all_unique(self.input.as_str(), 4).ok_or_else(|| anyhow!("no position found"))
     }
 
     fn part2(&self) -> Result<usize> {
@@ -94,14 +60,27 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part1() -> Result<()> {
-        assert_eq!(7, find_pos("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4)?);
-        assert_eq!(5, find_pos("bvwbjplbgvbhsrlpgdmjqwftvncz", 4)?);
-        assert_eq!(6, find_pos("nppdvjthqldpwncqszvftbrmjlhg", 4)?);
-        assert_eq!(10, find_pos("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4)?);
-        assert_eq!(11, find_pos("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4)?);
-
-        Ok(())
+    fn test_part1() {
+        assert_eq!(
+            Some(7),
+            first_window_all_unique("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4)
+        );
+        assert_eq!(
+            Some(5),
+            first_window_all_unique("bvwbjplbgvbhsrlpgdmjqwftvncz", 4)
+        );
+        assert_eq!(
+            Some(6),
+            first_window_all_unique("nppdvjthqldpwncqszvftbrmjlhg", 4)
+        );
+        assert_eq!(
+            Some(10),
+            first_window_all_unique("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4)
+        );
+        assert_eq!(
+            Some(11),
+            first_window_all_unique("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4)
+        );
     }
 
     #[test]
@@ -24,46 +24,11 @@ impl FromStr for Problem {
 }
 
 impl Problem {
-    fn is_visible_from(&self, pt: &Point2D, direction: Direction) -> bool {
-        let height = self.forest[*pt];
-        let mut location = pt.cardinal_neighbor(direction, self.forest.bounds);
-
-        while let Some(loc) = location {
-            if self.forest[loc] >= height {
-                return false;
-            }
-            location = loc.cardinal_neighbor(direction, self.forest.bounds);
-        }
-        true
-    }
-
-    fn is_visible(&self, pt: &Point2D) -> bool {
-        self.is_visible_from(pt, Left)
-            || self.is_visible_from(pt, Right)
-            || self.is_visible_from(pt, Up)
-            || self.is_visible_from(pt, Down)
-    }
-
-    fn viewing_distance(&self, pt: &Point2D, direction: Direction) -> u32 {
-        let height = self.forest[*pt];
-        let mut location = pt.cardinal_neighbor(direction, self.forest.bounds);
-        let mut distance = 0;
-
-        while let Some(loc) = location {
-            distance += 1;
-            if self.forest[loc] >= height {
-                break;
-            }
-            location = loc.cardinal_neighbor(direction, self.forest.bounds);
-        }
-        distance
-    }
-
     fn viewing_score(&self, pt: &Point2D) -> u32 {
-        self.viewing_distance(pt, Left)
-            * self.viewing_distance(pt, Right)
-            * self.viewing_distance(pt, Up)
-            * self.viewing_distance(pt, Down)
+        [Left, Right, Up, Down]
+            .into_iter()
+            .map(|dir| self.forest.viewing_distance(*pt, dir) as u32)
+            .product()
     }
 }
 
@@ -76,7 +41,7 @@ impl Solution for Problem {
             .forest
             .bounds
             .iter_horizontal()
-            .filter(|pt| self.is_visible(pt))
+            .filter(|pt| self.forest.visible_from_edge(*pt))
             .count())
     }
 
@@ -109,10 +74,10 @@ mod tests {
     fn test_viewing_distance() -> Result<()> {
         let problem = Problem::from_str(SAMPLE)?;
 
-        assert_eq!(1, problem.viewing_distance(&pt(2, 1), Left));
-        assert_eq!(2, problem.viewing_distance(&pt(2, 1), Right));
-        assert_eq!(1, problem.viewing_distance(&pt(2, 1), Up));
-        assert_eq!(2, problem.viewing_distance(&pt(2, 1), Down));
+        assert_eq!(1, problem.forest.viewing_distance(pt(2, 1), Left));
+        assert_eq!(2, problem.forest.viewing_distance(pt(2, 1), Right));
+        assert_eq!(1, problem.forest.viewing_distance(pt(2, 1), Up));
+        assert_eq!(2, problem.forest.viewing_distance(pt(2, 1), Down));
 
         Ok(())
     }
@@ -1,9 +1,11 @@
 use std::{
-    cmp::{Eq, Ord, Ordering, PartialOrd},
-    collections::BinaryHeap,
+    cmp::{Eq, Ord, Ordering, PartialOrd, Reverse},
+    collections::{BinaryHeap, HashSet, VecDeque},
+    hash::Hash,
     ops::Add,
 };
 
+use anyhow::*;
 use fnv::FnvHashMap;
 
 pub trait OptimizationState {
@@ -94,6 +96,609 @@ where
     None
 }
 
+// Same as dijkstra, but returns every state along the way from start to the goal. States are
+// remembered by cache key, so when several states share a key this is just one of the optimal
+// paths
+pub fn dijkstra_path<TState, FNext, TI, FFinal>(
+    start_state: TState,
+    next: FNext,
+    final_predicate: FFinal,
+) -> Option<Vec<TState>>
+where
+    TState: OptimizationState + Clone,
+    FNext: Fn(&TState) -> TI,
+    TI: IntoIterator<Item = TState>,
+    FFinal: Fn(&TState) -> bool,
+{
+    let start_key = start_state.cache_key();
+    let mut cache: FnvHashMap<TState::CacheKey, TState::Score> = FnvHashMap::default();
+    let mut predecessors: FnvHashMap<TState::CacheKey, TState> = FnvHashMap::default();
+    let mut heap: BinaryHeap<OptimizationStateWrapper<TState>> = BinaryHeap::new();
+    cache.insert(start_state.cache_key(), start_state.score());
+    heap.push(OptimizationStateWrapper(start_state));
+
+    while let Some(OptimizationStateWrapper(state)) = heap.pop() {
+        if final_predicate(&state) {
+            return Some(reconstruct_path(state, &predecessors, &start_key));
+        }
+
+        match cache.get(&state.cache_key()) {
+            Some(prev_score) if state.score() < *prev_score => {
+                continue;
+            }
+            _ => (),
+        }
+
+        for next in next(&state) {
+            let key = next.cache_key();
+            let score = next.score();
+            match cache.get(&key) {
+                Some(prev_score) if score <= *prev_score => (),
+                _ => {
+                    predecessors.insert(next.cache_key(), state.clone());
+                    cache.insert(key, score);
+                    heap.push(OptimizationStateWrapper(next));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn reconstruct_path<TState, TKey>(
+    end_state: TState,
+    predecessors: &FnvHashMap<TKey, TState>,
+    start_key: &TKey,
+) -> Vec<TState>
+where
+    TState: Clone,
+    TState: OptimizationState<CacheKey = TKey>,
+    TKey: Eq + Hash,
+{
+    let mut path = vec![end_state];
+    loop {
+        let key = path[path.len() - 1].cache_key();
+        if key == *start_key {
+            break;
+        }
+        match predecessors.get(&key) {
+            Some(prev) => path.push(prev.clone()),
+            None => break,
+        }
+    }
+    path.reverse();
+    path
+}
+
+// Same as dijkstra, but runs until every reachable state has been explored and returns the best
+// score seen for each cache key (including the start)
+pub fn dijkstra_all<TState, FNext, TI>(
+    start_state: TState,
+    next: FNext,
+) -> FnvHashMap<TState::CacheKey, TState::Score>
+where
+    TState: OptimizationState,
+    FNext: Fn(&TState) -> TI,
+    TI: IntoIterator<Item = TState>,
+{
+    let mut cache: FnvHashMap<TState::CacheKey, TState::Score> = FnvHashMap::default();
+    let mut heap: BinaryHeap<OptimizationStateWrapper<TState>> = BinaryHeap::new();
+    cache.insert(start_state.cache_key(), start_state.score());
+    heap.push(OptimizationStateWrapper(start_state));
+
+    while let Some(OptimizationStateWrapper(state)) = heap.pop() {
+        match cache.get(&state.cache_key()) {
+            Some(prev_score) if state.score() < *prev_score => {
+                continue;
+            }
+            _ => (),
+        }
+
+        for next in next(&state) {
+            let key = next.cache_key();
+            let score = next.score();
+            match cache.get(&key) {
+                Some(prev_score) if score <= *prev_score => (),
+                _ => {
+                    cache.insert(key, score);
+                    heap.push(OptimizationStateWrapper(next));
+                }
+            }
+        }
+    }
+
+    cache
+}
+
+// Breadth-first search for when every step costs the same, returns the number of steps taken to
+// reach the goal along with the goal state. States are deduplicated on the whole state, so it
+// should not include anything like a step counter
+pub fn bfs<TState, FNext, TI, FFinal>(
+    start_state: TState,
+    next: FNext,
+    is_goal: FFinal,
+) -> Option<(usize, TState)>
+where
+    TState: Clone + Eq + Hash,
+    FNext: Fn(&TState) -> TI,
+    TI: IntoIterator<Item = TState>,
+    FFinal: Fn(&TState) -> bool,
+{
+    let mut seen: HashSet<TState> = HashSet::new();
+    let mut todo: VecDeque<(usize, TState)> = VecDeque::new();
+    seen.insert(start_state.clone());
+    todo.push_back((0, start_state));
+
+    while let Some((steps, state)) = todo.pop_front() {
+        if is_goal(&state) {
+            return Some((steps, state));
+        }
+
+        for next in next(&state) {
+            if !seen.contains(&next) {
+                seen.insert(next.clone());
+                todo.push_back((steps + 1, next));
+            }
+        }
+    }
+
+    None
+}
+
+// Beam search: expands the states one step at a time like bfs, but only keeps the beam_width
+// best scoring states (highest first, like dijkstra) from each round. This is a heuristic, a
+// state that looks bad early on may be dropped even if it leads to the best answer, so it can
+// miss the optimum or fail to reach a goal at all. Widening the beam trades speed for accuracy.
+// When goals show up in a round, the best scoring one is returned
+pub fn beam_search<TState, FNext, TI, FFinal>(
+    start_state: TState,
+    next: FNext,
+    is_goal: FFinal,
+    beam_width: usize,
+) -> Option<TState>
+where
+    TState: OptimizationState + Clone,
+    FNext: Fn(&TState) -> TI,
+    TI: IntoIterator<Item = TState>,
+    FFinal: Fn(&TState) -> bool,
+{
+    let mut cache: FnvHashMap<TState::CacheKey, TState::Score> = FnvHashMap::default();
+    cache.insert(start_state.cache_key(), start_state.score());
+    let mut beam = vec![start_state];
+
+    while !beam.is_empty() {
+        if let Some(goal) = beam
+            .iter()
+            .filter(|state| is_goal(state))
+            .max_by(|a, b| a.score().cmp(&b.score()))
+        {
+            return Some(goal.clone());
+        }
+
+        // best state for each key reached this round
+        let mut round: FnvHashMap<TState::CacheKey, TState> = FnvHashMap::default();
+        for state in &beam {
+            for next in next(state) {
+                let key = next.cache_key();
+                let score = next.score();
+                match cache.get(&key) {
+                    Some(prev_score) if score <= *prev_score => (),
+                    _ => {
+                        cache.insert(next.cache_key(), score);
+                        round.insert(key, next);
+                    }
+                }
+            }
+        }
+
+        beam = round.into_values().collect();
+        beam.sort_by_key(|state| Reverse(state.score()));
+        beam.truncate(beam_width);
+    }
+
+    None
+}
+
+// Iterative deepening depth-first search, for when the state space is too wide to keep a bfs
+// queue around. Finds the shallowest goal up to max_depth, at the cost of re-exploring the
+// shallower levels each time the limit goes up. States aren't deduplicated
+pub fn iddfs<TState, FNext, TI, FFinal>(
+    start_state: TState,
+    next: FNext,
+    is_goal: FFinal,
+    max_depth: usize,
+) -> Option<(usize, TState)>
+where
+    TState: Clone,
+    FNext: Fn(&TState) -> TI,
+    TI: IntoIterator<Item = TState>,
+    FFinal: Fn(&TState) -> bool,
+{
+    fn depth_limited<TState, FNext, TI, FFinal>(
+        state: &TState,
+        next: &FNext,
+        is_goal: &FFinal,
+        remaining: usize,
+    ) -> Option<TState>
+    where
+        TState: Clone,
+        FNext: Fn(&TState) -> TI,
+        TI: IntoIterator<Item = TState>,
+        FFinal: Fn(&TState) -> bool,
+    {
+        if remaining == 0 {
+            return if is_goal(state) {
+                Some(state.clone())
+            } else {
+                None
+            };
+        }
+
+        next(state)
+            .into_iter()
+            .find_map(|next_state| depth_limited(&next_state, next, is_goal, remaining - 1))
+    }
+
+    (0..=max_depth).find_map(|depth| {
+        depth_limited(&start_state, &next, &is_goal, depth).map(|goal| (depth, goal))
+    })
+}
+
+// Kahn's algorithm. Each edge (a, b) means a must come before b. When more than one node is
+// available, the one earliest in nodes goes first so the order is deterministic
+pub fn topo_sort<T>(nodes: &[T], edges: &[(T, T)]) -> Result<Vec<T>>
+where
+    T: Clone + Eq + Hash,
+{
+    let indexes: FnvHashMap<&T, usize> = nodes.iter().enumerate().map(|(ix, n)| (n, ix)).collect();
+    let mut dependents: Vec<Vec<usize>> = vec![vec![]; nodes.len()];
+    let mut incoming = vec![0; nodes.len()];
+
+    for (before, after) in edges {
+        let before = *indexes
+            .get(before)
+            .ok_or_else(|| anyhow!("edge references a node that isn't in the node list"))?;
+        let after = *indexes
+            .get(after)
+            .ok_or_else(|| anyhow!("edge references a node that isn't in the node list"))?;
+        dependents[before].push(after);
+        incoming[after] += 1;
+    }
+
+    let mut ready: BinaryHeap<Reverse<usize>> = incoming
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count == 0)
+        .map(|(ix, _)| Reverse(ix))
+        .collect();
+    let mut sorted = Vec::with_capacity(nodes.len());
+
+    while let Some(Reverse(ix)) = ready.pop() {
+        sorted.push(nodes[ix].clone());
+        for &dependent in &dependents[ix] {
+            incoming[dependent] -= 1;
+            if incoming[dependent] == 0 {
+                ready.push(Reverse(dependent));
+            }
+        }
+    }
+
+    if sorted.len() != nodes.len() {
+        bail!("graph contains a cycle");
+    }
+
+    Ok(sorted)
+}
+
+// Tarjan's strongly connected components. Every node ends up in exactly one component, and
+// components come out in reverse topological order (a component only has edges into ones that
+// came before it). Panics if an edge references a node that isn't in nodes
+pub fn scc<T>(nodes: &[T], edges: &[(T, T)]) -> Vec<Vec<T>>
+where
+    T: Clone + Eq + Hash,
+{
+    struct Tarjan {
+        adjacent: Vec<Vec<usize>>,
+        index: Vec<Option<usize>>,
+        low_link: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        next_index: usize,
+        components: Vec<Vec<usize>>,
+    }
+
+    impl Tarjan {
+        fn connect(&mut self, node: usize) {
+            self.index[node] = Some(self.next_index);
+            self.low_link[node] = self.next_index;
+            self.next_index += 1;
+            self.stack.push(node);
+            self.on_stack[node] = true;
+
+            for ix in 0..self.adjacent[node].len() {
+                let next = self.adjacent[node][ix];
+                match self.index[next] {
+                    None => {
+                        self.connect(next);
+                        self.low_link[node] = self.low_link[node].min(self.low_link[next]);
+                    }
+                    Some(next_index) if self.on_stack[next] => {
+                        self.low_link[node] = self.low_link[node].min(next_index);
+                    }
+                    _ => (),
+                }
+            }
+
+            if Some(self.low_link[node]) == self.index[node] {
+                let mut component = vec![];
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+
+    let indexes: FnvHashMap<&T, usize> = nodes.iter().enumerate().map(|(ix, n)| (n, ix)).collect();
+    let mut adjacent: Vec<Vec<usize>> = vec![vec![]; nodes.len()];
+    for (from, to) in edges {
+        let from = indexes[from];
+        let to = indexes[to];
+        adjacent[from].push(to);
+    }
+
+    let mut tarjan = Tarjan {
+        adjacent,
+        index: vec![None; nodes.len()],
+        low_link: vec![0; nodes.len()],
+        on_stack: vec![false; nodes.len()],
+        stack: vec![],
+        next_index: 0,
+        components: vec![],
+    };
+    for node in 0..nodes.len() {
+        if tarjan.index[node].is_none() {
+            tarjan.connect(node);
+        }
+    }
+
+    tarjan
+        .components
+        .into_iter()
+        .map(|component| component.into_iter().map(|ix| nodes[ix].clone()).collect())
+        .collect()
+}
+
+// Runs a deterministic simulation until a state repeats, returning (start_index, length) of the
+// cycle. The state at step n >= start_index is the same as the state at
+// start_index + (n - start_index) % length
+pub fn find_cycle<T, F>(start: T, mut step: F) -> (usize, usize)
+where
+    T: Clone + Eq + Hash,
+    F: FnMut(&T) -> T,
+{
+    let mut seen: FnvHashMap<T, usize> = FnvHashMap::default();
+    let mut state = start;
+    let mut ix = 0;
+
+    loop {
+        if let Some(first_seen) = seen.get(&state) {
+            return (*first_seen, ix - first_seen);
+        }
+        let next = step(&state);
+        seen.insert(state, ix);
+        state = next;
+        ix += 1;
+    }
+}
+
+// Calls step on state up to max_steps times, stopping as soon as it returns true (the simulation
+// settled, hit its goal, etc). Returns how many steps were run, counting the one that returned
+// true, so the result is max_steps when the cap was reached
+pub fn simulate<S, F>(state: &mut S, max_steps: usize, mut step: F) -> usize
+where
+    F: FnMut(&mut S) -> bool,
+{
+    for steps in 1..=max_steps {
+        if step(state) {
+            return steps;
+        }
+    }
+    max_steps
+}
+
+// Smallest value in [lo, hi] where pred is true, assuming pred is false up to some point and true
+// from there on
+pub fn binary_search_first<F>(lo: i64, hi: i64, mut pred: F) -> Option<i64>
+where
+    F: FnMut(i64) -> bool,
+{
+    if lo > hi || !pred(hi) {
+        return None;
+    }
+
+    // pred(hi) is known to be true, narrow down until lo == hi
+    let (mut lo, mut hi) = (lo, hi);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+
+    Some(lo)
+}
+
+// All pairs shortest paths over nodes 0..n with directed, weighted edges. result[a][b] is None
+// when b can't be reached from a
+pub fn floyd_warshall(n: usize, edges: &[(usize, usize, i64)]) -> Vec<Vec<Option<i64>>> {
+    let mut dist: Vec<Vec<Option<i64>>> = vec![vec![None; n]; n];
+    for (ix, row) in dist.iter_mut().enumerate() {
+        row[ix] = Some(0);
+    }
+    for &(from, to, weight) in edges {
+        dist[from][to] = Some(dist[from][to].map_or(weight, |d| d.min(weight)));
+    }
+
+    for k in 0..n {
+        let from_k = dist[k].clone();
+        for row in dist.iter_mut() {
+            let Some(via) = row[k] else {
+                continue;
+            };
+            for (cell, rest) in row.iter_mut().zip(&from_k) {
+                if let Some(rest) = rest {
+                    let through = via + rest;
+                    if !matches!(cell, Some(d) if *d <= through) {
+                        *cell = Some(through);
+                    }
+                }
+            }
+        }
+    }
+
+    dist
+}
+
+// Shortest distances from source over nodes 0..n, allowing negative edge weights. Unreachable
+// nodes are None, and the whole result is None if a negative cycle can be reached from source
+pub fn bellman_ford(
+    n: usize,
+    edges: &[(usize, usize, i64)],
+    source: usize,
+) -> Option<Vec<Option<i64>>> {
+    let mut dist: Vec<Option<i64>> = vec![None; n];
+    dist[source] = Some(0);
+
+    for _ in 1..n {
+        let mut changed = false;
+        for &(from, to, weight) in edges {
+            if let Some(d) = dist[from] {
+                if !matches!(dist[to], Some(prev) if prev <= d + weight) {
+                    dist[to] = Some(d + weight);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    // anything that can still be improved is on or after a negative cycle
+    for &(from, to, weight) in edges {
+        if let (Some(d), Some(prev)) = (dist[from], dist[to]) {
+            if d + weight < prev {
+                return None;
+            }
+        }
+    }
+
+    Some(dist)
+}
+
+// Edmonds-Karp over an adjacency matrix of capacities. Undirected edges get their capacity in
+// both directions. Returns the max flow and the remaining residual capacities
+fn edmonds_karp(
+    n: usize,
+    edges: &[(usize, usize, u64)],
+    source: usize,
+    sink: usize,
+    directed: bool,
+) -> (u64, Vec<Vec<u64>>) {
+    let mut residual = vec![vec![0u64; n]; n];
+    for &(from, to, capacity) in edges {
+        residual[from][to] += capacity;
+        if !directed {
+            residual[to][from] += capacity;
+        }
+    }
+
+    let mut flow = 0;
+    loop {
+        // shortest augmenting path by bfs
+        let mut parent: Vec<Option<usize>> = vec![None; n];
+        parent[source] = Some(source);
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            if node == sink {
+                break;
+            }
+            for next in 0..n {
+                if parent[next].is_none() && residual[node][next] > 0 {
+                    parent[next] = Some(node);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if parent[sink].is_none() || source == sink {
+            return (flow, residual);
+        }
+
+        let mut bottleneck = u64::MAX;
+        let mut node = sink;
+        while node != source {
+            let prev = parent[node].unwrap();
+            bottleneck = bottleneck.min(residual[prev][node]);
+            node = prev;
+        }
+
+        let mut node = sink;
+        while node != source {
+            let prev = parent[node].unwrap();
+            residual[prev][node] -= bottleneck;
+            residual[node][prev] += bottleneck;
+            node = prev;
+        }
+
+        flow += bottleneck;
+    }
+}
+
+pub fn max_flow(
+    n: usize,
+    edges: &[(usize, usize, u64)],
+    source: usize,
+    sink: usize,
+    directed: bool,
+) -> u64 {
+    edmonds_karp(n, edges, source, sink, directed).0
+}
+
+// nodes still reachable from source once the max flow is pushed through, in order. The edges
+// leaving this set make up a minimum cut
+pub fn min_cut_partition(
+    n: usize,
+    edges: &[(usize, usize, u64)],
+    source: usize,
+    sink: usize,
+    directed: bool,
+) -> Vec<usize> {
+    let (_, residual) = edmonds_karp(n, edges, source, sink, directed);
+
+    let mut seen = vec![false; n];
+    seen[source] = true;
+    let mut queue = VecDeque::from([source]);
+    while let Some(node) = queue.pop_front() {
+        for next in 0..n {
+            if !seen[next] && residual[node][next] > 0 {
+                seen[next] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+
+    (0..n).filter(|node| seen[*node]).collect()
+}
+
 pub trait AStarState {
     type CacheKey: Eq + std::hash::Hash;
     type Score: Eq + Ord;
@@ -215,3 +820,1081 @@ where
 
     None
 }
+
+// lossy conversion for weighting scores, `as f64` isn't available through a generic bound and
+// Into<f64> leaves out usize, u64 and i64
+pub trait WeightedScore: Copy {
+    fn as_f64(self) -> f64;
+}
+
+macro_rules! impl_weighted_score {
+    ($($t:ty),*) => {
+        $(
+            impl WeightedScore for $t {
+                fn as_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_weighted_score!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+struct WeightedStateWrapper<TState> {
+    state: TState,
+    priority: f64,
+}
+
+impl<TState> PartialEq for WeightedStateWrapper<TState> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority.total_cmp(&other.priority) == Ordering::Equal
+    }
+}
+impl<TState> Eq for WeightedStateWrapper<TState> {}
+
+impl<TState> Ord for WeightedStateWrapper<TState> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // comparing in reverse to minimize instead of maximize
+        other.priority.total_cmp(&self.priority)
+    }
+}
+
+impl<TState> PartialOrd for WeightedStateWrapper<TState> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Same as a_star, but the heuristic is multiplied by weight when ordering the queue. weight = 1.0
+// is plain a_star; weight > 1 leans towards greedy best-first search, which usually expands far
+// fewer states but may return a path that isn't the cheapest
+pub fn a_star_weighted<TState, FNext, TI, FHeuristic, FFinal>(
+    start_state: TState,
+    next: FNext,
+    h: FHeuristic,
+    final_predicate: FFinal,
+    weight: f64,
+) -> Option<TState>
+where
+    TState: OptimizationState,
+    FNext: Fn(&TState) -> TI,
+    TI: IntoIterator<Item = TState>,
+    FHeuristic: Fn(&TState) -> <TState as OptimizationState>::Score,
+    FFinal: Fn(&TState) -> bool,
+    <TState as OptimizationState>::Score: WeightedScore,
+{
+    let mut cache: FnvHashMap<TState::CacheKey, TState::Score> = FnvHashMap::default();
+    let mut heap: BinaryHeap<WeightedStateWrapper<TState>> = BinaryHeap::new();
+    heap.push(WeightedStateWrapper {
+        priority: start_state.score().as_f64(),
+        state: start_state,
+    });
+
+    while let Some(WeightedStateWrapper { state, priority: _ }) = heap.pop() {
+        if final_predicate(&state) {
+            return Some(state);
+        }
+
+        match cache.get(&state.cache_key()) {
+            Some(prev_score) if state.score() > *prev_score => {
+                continue;
+            }
+            _ => (),
+        }
+
+        for next in next(&state) {
+            let key = next.cache_key();
+            let score = next.score();
+            match cache.get(&key) {
+                Some(prev_score) if score >= *prev_score => (),
+                _ => {
+                    cache.insert(key, score);
+                    let priority = score.as_f64() + weight * h(&next).as_f64();
+                    heap.push(WeightedStateWrapper {
+                        state: next,
+                        priority,
+                    });
+                }
+            }
+        }
+    }
+
+    None
+}
+
+// Same as a_star, but returns every state along the way from start to the goal, like
+// dijkstra_path
+pub fn a_star_path<TState, FNext, TI, FHeuristic, FFinal>(
+    start_state: TState,
+    next: FNext,
+    h: FHeuristic,
+    final_predicate: FFinal,
+) -> Option<Vec<TState>>
+where
+    TState: OptimizationState + Clone,
+    FNext: Fn(&TState) -> TI,
+    TI: IntoIterator<Item = TState>,
+    FHeuristic: Fn(&TState) -> <TState as OptimizationState>::Score,
+    FFinal: Fn(&TState) -> bool,
+    <TState as OptimizationState>::Score:
+        Default + Copy + Add<Output = <TState as OptimizationState>::Score>,
+{
+    let start_key = start_state.cache_key();
+    let mut cache: FnvHashMap<TState::CacheKey, TState::Score> = FnvHashMap::default();
+    let mut predecessors: FnvHashMap<TState::CacheKey, TState> = FnvHashMap::default();
+    let mut heap: BinaryHeap<AStarStateWrapper<TState>> = BinaryHeap::new();
+    cache.insert(start_state.cache_key(), start_state.score());
+    heap.push(AStarStateWrapper {
+        state: start_state,
+        heuristic: Default::default(),
+    });
+
+    while let Some(AStarStateWrapper {
+        state,
+        heuristic: _,
+    }) = heap.pop()
+    {
+        if final_predicate(&state) {
+            return Some(reconstruct_path(state, &predecessors, &start_key));
+        }
+
+        match cache.get(&state.cache_key()) {
+            Some(prev_score) if state.score() > *prev_score => {
+                continue;
+            }
+            _ => (),
+        }
+
+        for next in next(&state) {
+            let key = next.cache_key();
+            let score = next.score();
+            match cache.get(&key) {
+                Some(prev_score) if score >= *prev_score => (),
+                _ => {
+                    predecessors.insert(next.cache_key(), state.clone());
+                    cache.insert(key, score);
+                    let heuristic = h(&next);
+                    heap.push(AStarStateWrapper {
+                        state: next,
+                        heuristic,
+                    });
+                }
+            }
+        }
+    }
+
+    None
+}
+
+// Union-find over 0..n, for merging regions or groups
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    count: usize,
+}
+
+impl DisjointSet {
+    pub fn new(n: usize) -> DisjointSet {
+        DisjointSet {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            count: n,
+        }
+    }
+
+    // adds a new single-item set, returning its index
+    pub fn push(&mut self) -> usize {
+        let ix = self.parent.len();
+        self.parent.push(ix);
+        self.rank.push(0);
+        self.count += 1;
+        ix
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // path compression - point everything along the way directly at the root
+        let mut current = x;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    // returns false if a and b were already in the same set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let a = self.find(a);
+        let b = self.find(b);
+        if a == b {
+            return false;
+        }
+
+        match self.rank[a].cmp(&self.rank[b]) {
+            Ordering::Less => self.parent[a] = b,
+            Ordering::Greater => self.parent[b] = a,
+            Ordering::Equal => {
+                self.parent[b] = a;
+                self.rank[a] += 1;
+            }
+        }
+        self.count -= 1;
+
+        true
+    }
+
+    pub fn same_set(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    // number of distinct sets
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+// DisjointSet for arbitrary keys, keys are added as they are encountered
+#[derive(Clone, Debug)]
+pub struct DisjointSetMap<K> {
+    indexes: FnvHashMap<K, usize>,
+    sets: DisjointSet,
+}
+
+impl<K> Default for DisjointSetMap<K> {
+    fn default() -> Self {
+        Self {
+            indexes: FnvHashMap::default(),
+            sets: DisjointSet::new(0),
+        }
+    }
+}
+
+impl<K> DisjointSetMap<K>
+where
+    K: Hash + Eq,
+{
+    pub fn insert(&mut self, key: K) -> usize {
+        match self.indexes.get(&key) {
+            Some(ix) => *ix,
+            None => {
+                let ix = self.sets.push();
+                self.indexes.insert(key, ix);
+                ix
+            }
+        }
+    }
+
+    // returns the index of the set's root, or None if the key has never been seen
+    pub fn find(&mut self, key: &K) -> Option<usize> {
+        let ix = *self.indexes.get(key)?;
+        Some(self.sets.find(ix))
+    }
+
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let a = self.insert(a);
+        let b = self.insert(b);
+        self.sets.union(a, b)
+    }
+
+    pub fn same_set(&mut self, a: &K, b: &K) -> bool {
+        match (self.find(a), self.find(b)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.sets.count()
+    }
+}
+
+// Cache for recursive solutions. The compute function gets the memo back so it can recurse
+// through it instead of threading a HashMap through every call
+pub struct Memo<K, V> {
+    cache: FnvHashMap<K, V>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo {
+            cache: FnvHashMap::default(),
+        }
+    }
+}
+
+impl<K, V> Memo<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    pub fn new() -> Memo<K, V> {
+        Memo::default()
+    }
+
+    pub fn get_or_compute<F>(&mut self, key: K, f: F) -> V
+    where
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+
+        let value = f(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+// Min-heap where every key is in the queue at most once, so priorities can be lowered in place
+// instead of pushing duplicates and skipping the stale ones on the way out like dijkstra does
+pub struct IndexedPriorityQueue<K, P> {
+    heap: Vec<(K, P)>,
+    positions: FnvHashMap<K, usize>,
+}
+
+impl<K, P> Default for IndexedPriorityQueue<K, P> {
+    fn default() -> Self {
+        IndexedPriorityQueue {
+            heap: Vec::new(),
+            positions: FnvHashMap::default(),
+        }
+    }
+}
+
+impl<K, P> IndexedPriorityQueue<K, P>
+where
+    K: Hash + Eq + Clone,
+    P: Ord,
+{
+    pub fn new() -> IndexedPriorityQueue<K, P> {
+        IndexedPriorityQueue::default()
+    }
+
+    // adds key, or replaces its priority if it's already queued
+    pub fn push(&mut self, key: K, priority: P) {
+        if let Some(&ix) = self.positions.get(&key) {
+            let lower = priority < self.heap[ix].1;
+            self.heap[ix].1 = priority;
+            if lower {
+                self.sift_up(ix);
+            } else {
+                self.sift_down(ix);
+            }
+            return;
+        }
+
+        self.positions.insert(key.clone(), self.heap.len());
+        self.heap.push((key, priority));
+        self.sift_up(self.heap.len() - 1);
+    }
+
+    pub fn pop_min(&mut self) -> Option<(K, P)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let last = self.heap.len() - 1;
+        self.swap(0, last);
+        let (key, priority) = self.heap.pop()?;
+        self.positions.remove(&key);
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+        Some((key, priority))
+    }
+
+    // lowers the priority of a queued key, returns false (and leaves the queue alone) if the key
+    // isn't queued or new isn't lower than its current priority
+    pub fn decrease_priority(&mut self, key: &K, new: P) -> bool {
+        match self.positions.get(key) {
+            Some(&ix) if new < self.heap[ix].1 => {
+                self.heap[ix].1 = new;
+                self.sift_up(ix);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn priority(&self, key: &K) -> Option<&P> {
+        self.positions.get(key).map(|ix| &self.heap[*ix].1)
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.positions.contains_key(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        *self.positions.get_mut(&self.heap[a].0).unwrap() = a;
+        *self.positions.get_mut(&self.heap[b].0).unwrap() = b;
+    }
+
+    fn sift_up(&mut self, mut ix: usize) {
+        while ix > 0 {
+            let parent = (ix - 1) / 2;
+            if self.heap[ix].1 >= self.heap[parent].1 {
+                break;
+            }
+            self.swap(ix, parent);
+            ix = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut ix: usize) {
+        loop {
+            let mut smallest = ix;
+            for child in [ix * 2 + 1, ix * 2 + 2] {
+                if child < self.heap.len() && self.heap[child].1 < self.heap[smallest].1 {
+                    smallest = child;
+                }
+            }
+            if smallest == ix {
+                break;
+            }
+            self.swap(ix, smallest);
+            ix = smallest;
+        }
+    }
+}
+
+// Kruskal's minimum spanning tree over nodes 0..n, returns the total weight and the edges used.
+// If the graph isn't connected this is a spanning forest, one tree per component, and will have
+// fewer than n - 1 edges
+pub fn mst_kruskal(n: usize, edges: &[(usize, usize, u64)]) -> (u64, Vec<(usize, usize)>) {
+    let mut sorted: Vec<&(usize, usize, u64)> = edges.iter().collect();
+    sorted.sort_by_key(|(_, _, weight)| *weight);
+
+    let mut sets = DisjointSet::new(n);
+    let mut total = 0;
+    let mut chosen = vec![];
+    for &(a, b, weight) in sorted {
+        if sets.union(a, b) {
+            total += weight;
+            chosen.push((a, b));
+            if chosen.len() + 1 == n {
+                break;
+            }
+        }
+    }
+
+    (total, chosen)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    struct NodeState {
+        node: usize,
+        cost: u32,
+    }
+
+    impl OptimizationState for NodeState {
+        type CacheKey = usize;
+        type Score = Reverse<u32>;
+
+        fn cache_key(&self) -> usize {
+            self.node
+        }
+
+        fn score(&self) -> Reverse<u32> {
+            Reverse(self.cost)
+        }
+    }
+
+    // 0 -> 1 (4), 0 -> 2 (1), 2 -> 1 (2), 1 -> 3 (1), 2 -> 3 (5), 4 is unreachable
+    const EDGES: [(usize, usize, u32); 5] = [(0, 1, 4), (0, 2, 1), (2, 1, 2), (1, 3, 1), (2, 3, 5)];
+
+    fn next_nodes(state: &NodeState) -> Vec<NodeState> {
+        EDGES
+            .iter()
+            .filter(|(from, _, _)| *from == state.node)
+            .map(|&(_, node, cost)| NodeState {
+                node,
+                cost: state.cost + cost,
+            })
+            .collect()
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    struct GridState {
+        x: usize,
+        y: usize,
+        cost: u32,
+    }
+
+    impl OptimizationState for GridState {
+        type CacheKey = (usize, usize);
+        type Score = Reverse<u32>;
+
+        fn cache_key(&self) -> (usize, usize) {
+            (self.x, self.y)
+        }
+
+        fn score(&self) -> Reverse<u32> {
+            Reverse(self.cost)
+        }
+    }
+
+    fn grid_moves(state: &GridState, costs: &[[u32; 3]; 3]) -> Vec<GridState> {
+        [(0, 1), (2, 1), (1, 0), (1, 2)]
+            .into_iter()
+            .filter_map(|(dx, dy)| {
+                let x = (state.x + dx).checked_sub(1).filter(|x| *x < 3)?;
+                let y = (state.y + dy).checked_sub(1).filter(|y| *y < 3)?;
+                Some(GridState {
+                    x,
+                    y,
+                    cost: state.cost + costs[y][x],
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn beam_search_on_grid() {
+        let costs = [[1, 1, 5], [9, 1, 9], [9, 1, 1]];
+        let start = GridState {
+            x: 0,
+            y: 0,
+            cost: 0,
+        };
+        let is_goal = |state: &GridState| state.x == 2 && state.y == 2;
+
+        let best = dijkstra(start, |state| grid_moves(state, &costs), is_goal).unwrap();
+        let found = beam_search(start, |state| grid_moves(state, &costs), is_goal, 3).unwrap();
+        assert_eq!(found.cost, best.cost);
+        assert_eq!(found.cost, 4);
+
+        assert!(beam_search(start, |state| grid_moves(state, &costs), |_| false, 3).is_none());
+        assert!(beam_search(start, |state| grid_moves(state, &costs), is_goal, 0).is_none());
+    }
+
+    #[test]
+    fn dijkstra_path_on_grid() {
+        // 3x3 grid, cost of entering a cell is its value
+        let costs = [[1, 1, 5], [9, 1, 9], [9, 1, 1]];
+        let path = dijkstra_path(
+            GridState {
+                x: 0,
+                y: 0,
+                cost: 0,
+            },
+            |state| grid_moves(state, &costs),
+            |state| state.x == 2 && state.y == 2,
+        )
+        .unwrap();
+
+        assert_eq!((path[0].x, path[0].y), (0, 0));
+        let end = path[path.len() - 1];
+        assert_eq!((end.x, end.y), (2, 2));
+        assert_eq!(end.cost, 4);
+        assert_eq!(path.len(), 5);
+        assert!(path.windows(2).all(|pair| pair[0].cost < pair[1].cost));
+        assert!(path
+            .windows(2)
+            .all(|pair| pair[0].x.abs_diff(pair[1].x) + pair[0].y.abs_diff(pair[1].y) == 1));
+    }
+
+    // a_star scores can't be reversed, so wrap GridState with a plain score
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    struct AStarGridState(GridState);
+
+    impl OptimizationState for AStarGridState {
+        type CacheKey = (usize, usize);
+        type Score = u32;
+
+        fn cache_key(&self) -> (usize, usize) {
+            (self.0.x, self.0.y)
+        }
+
+        fn score(&self) -> u32 {
+            self.0.cost
+        }
+    }
+
+    #[derive(Copy, Clone)]
+    struct UsizeGridState(GridState);
+
+    impl OptimizationState for UsizeGridState {
+        type CacheKey = (usize, usize);
+        type Score = usize;
+
+        fn cache_key(&self) -> (usize, usize) {
+            (self.0.x, self.0.y)
+        }
+
+        fn score(&self) -> usize {
+            self.0.cost as usize
+        }
+    }
+
+    #[test]
+    fn a_star_path_on_grid() {
+        let costs = [[1, 1, 5], [9, 1, 9], [9, 1, 1]];
+        let path = a_star_path(
+            AStarGridState(GridState {
+                x: 0,
+                y: 0,
+                cost: 0,
+            }),
+            |state| {
+                grid_moves(&state.0, &costs)
+                    .into_iter()
+                    .map(AStarGridState)
+                    .collect::<Vec<_>>()
+            },
+            |state| (4 - state.0.x - state.0.y) as u32,
+            |state| state.0.x == 2 && state.0.y == 2,
+        )
+        .unwrap();
+
+        assert_eq!(path[0].cache_key(), (0, 0));
+        let end = path[path.len() - 1];
+        assert_eq!(end.cache_key(), (2, 2));
+        assert_eq!(end.score(), 4);
+        let step_costs: u32 = path[1..].iter().map(|s| costs[s.0.y][s.0.x]).sum();
+        assert_eq!(step_costs, end.score());
+    }
+
+    #[test]
+    fn a_star_weighted_on_grid() {
+        let costs = [[1, 1, 5], [9, 1, 9], [9, 1, 1]];
+        let start = || {
+            AStarGridState(GridState {
+                x: 0,
+                y: 0,
+                cost: 0,
+            })
+        };
+        let next = |state: &AStarGridState| {
+            grid_moves(&state.0, &costs)
+                .into_iter()
+                .map(AStarGridState)
+                .collect::<Vec<_>>()
+        };
+        let h = |state: &AStarGridState| (4 - state.0.x - state.0.y) as u32;
+        let goal = |state: &AStarGridState| state.0.x == 2 && state.0.y == 2;
+
+        let expected = a_star(start(), next, h, goal).unwrap().score();
+        let unweighted = a_star_weighted(start(), next, h, goal, 1.0).unwrap();
+        assert_eq!(unweighted.score(), expected);
+
+        let greedy = a_star_weighted(start(), next, h, goal, 5.0).unwrap();
+        assert_eq!(greedy.cache_key(), (2, 2));
+        assert!(greedy.score() >= expected);
+
+        // same search with the usize scores most solutions use
+        let found = a_star_weighted(
+            UsizeGridState(start().0),
+            |state: &UsizeGridState| {
+                grid_moves(&state.0, &costs)
+                    .into_iter()
+                    .map(UsizeGridState)
+                    .collect::<Vec<_>>()
+            },
+            |state| 4 - state.0.x - state.0.y,
+            |state| state.0.x == 2 && state.0.y == 2,
+            1.0,
+        )
+        .unwrap();
+        assert_eq!(found.score(), expected as usize);
+    }
+
+    #[test]
+    fn floyd_warshall_distances() {
+        // 0 -> 1 (5), 0 -> 1 (4), 1 -> 2 (1), 2 -> 3 (2), 0 -> 3 (10), 3 -> 0 (1)
+        let edges = [
+            (0, 1, 5),
+            (0, 1, 4),
+            (1, 2, 1),
+            (2, 3, 2),
+            (0, 3, 10),
+            (3, 0, 1),
+        ];
+        let dist = floyd_warshall(5, &edges);
+
+        assert_eq!(dist[0][1], Some(4));
+        assert_eq!(dist[0][3], Some(7));
+        assert_eq!(dist[3][2], Some(6));
+        assert_eq!(dist[1][0], Some(4));
+        assert_eq!(dist[2][2], Some(0));
+        assert_eq!(dist[0][4], None);
+        assert_eq!(dist[4][4], Some(0));
+    }
+
+    #[test]
+    fn bellman_ford_negative_edge() {
+        // 0 -> 1 (4), 0 -> 2 (5), 2 -> 1 (-3), 1 -> 3 (2), 3 is unreachable from 4
+        let edges = [(0, 1, 4), (0, 2, 5), (2, 1, -3), (1, 3, 2), (4, 0, 1)];
+
+        let dist = bellman_ford(5, &edges, 0).unwrap();
+
+        assert_eq!(dist, vec![Some(0), Some(2), Some(5), Some(4), None]);
+    }
+
+    #[test]
+    fn bellman_ford_negative_cycle() {
+        // 1 -> 2 -> 3 -> 1 sums to -1
+        let edges = [(0, 1, 1), (1, 2, 2), (2, 3, -4), (3, 1, 1), (4, 0, 1)];
+
+        assert_eq!(bellman_ford(5, &edges, 0), None);
+        // a negative cycle that can't be reached from source doesn't matter
+        assert_eq!(
+            bellman_ford(5, &[(0, 1, 1), (2, 3, -4), (3, 2, 1)], 0),
+            Some(vec![Some(0), Some(1), None, None, None])
+        );
+    }
+
+    // classic CLRS flow network, max flow from 0 to 5 is 23
+    const FLOW_EDGES: [(usize, usize, u64); 9] = [
+        (0, 1, 16),
+        (0, 2, 13),
+        (1, 3, 12),
+        (2, 1, 4),
+        (2, 4, 14),
+        (3, 2, 9),
+        (3, 5, 20),
+        (4, 3, 7),
+        (4, 5, 4),
+    ];
+
+    #[test]
+    fn max_flow_directed() {
+        assert_eq!(max_flow(6, &FLOW_EDGES, 0, 5, true), 23);
+        assert_eq!(
+            min_cut_partition(6, &FLOW_EDGES, 0, 5, true),
+            vec![0, 1, 2, 4]
+        );
+    }
+
+    #[test]
+    fn min_cut_undirected() {
+        // two triangles joined by a single edge
+        let edges = [
+            (0, 1, 1),
+            (1, 2, 1),
+            (2, 0, 1),
+            (3, 4, 1),
+            (4, 5, 1),
+            (5, 3, 1),
+            (2, 3, 1),
+        ];
+
+        assert_eq!(max_flow(6, &edges, 0, 5, false), 1);
+        assert_eq!(min_cut_partition(6, &edges, 0, 5, false), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn iddfs_finds_shallowest_goal() {
+        // same state space as the bfs test, from 1 each step can either add one or double
+        let result = iddfs(1, |&n| [n + 1, n * 2], |&n| n == 10, 10);
+        assert_eq!(result, Some((4, 10)));
+
+        assert_eq!(iddfs(1, |&n| [n + 1, n * 2], |&n| n == 10, 3), None);
+        assert_eq!(iddfs(1, |&n| [n + 1, n * 2], |&n| n == 1, 0), Some((0, 1)));
+    }
+
+    #[test]
+    fn binary_search_first_threshold() {
+        assert_eq!(binary_search_first(0, 100, |n| n * n >= 50), Some(8));
+        assert_eq!(binary_search_first(-10, 10, |n| n >= -10), Some(-10));
+        assert_eq!(binary_search_first(-10, 10, |n| n >= 10), Some(10));
+        assert_eq!(binary_search_first(0, 100, |_| false), None);
+        assert_eq!(binary_search_first(5, 4, |_| true), None);
+    }
+
+    #[test]
+    fn mst_kruskal_weight() {
+        let edges = [
+            (0, 1, 4),
+            (0, 2, 1),
+            (1, 2, 2),
+            (1, 3, 5),
+            (2, 3, 8),
+            (3, 4, 3),
+            (2, 4, 9),
+        ];
+
+        let (total, chosen) = mst_kruskal(5, &edges);
+
+        assert_eq!(total, 11);
+        assert_eq!(chosen, vec![(0, 2), (1, 2), (3, 4), (1, 3)]);
+    }
+
+    #[test]
+    fn mst_kruskal_forest() {
+        // {0, 1} and {2, 3, 4} aren't connected
+        let edges = [(0, 1, 2), (2, 3, 1), (3, 4, 1), (2, 4, 5)];
+
+        let (total, chosen) = mst_kruskal(5, &edges);
+
+        assert_eq!(total, 4);
+        assert_eq!(chosen.len(), 3);
+    }
+
+    #[test]
+    fn scc_groups_cycle() {
+        // a -> b -> c -> a is a cycle, with a tail leading in (start) and one leading out (d, e)
+        let nodes = ["start", "a", "b", "c", "d", "e"];
+        let edges = [
+            ("start", "a"),
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("c", "d"),
+            ("d", "e"),
+        ];
+
+        let components: Vec<Vec<&str>> = scc(&nodes, &edges)
+            .into_iter()
+            .map(|mut c| {
+                c.sort();
+                c
+            })
+            .collect();
+
+        assert_eq!(
+            components,
+            vec![vec!["e"], vec!["d"], vec!["a", "b", "c"], vec!["start"]]
+        );
+    }
+
+    fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        memo.get_or_compute(n, |memo| {
+            if n < 2 {
+                n
+            } else {
+                fib(n - 1, memo) + fib(n - 2, memo)
+            }
+        })
+    }
+
+    #[test]
+    fn memo_fibonacci() {
+        let mut memo = Memo::new();
+        assert!(memo.is_empty());
+
+        assert_eq!(fib(80, &mut memo), 23416728348467685);
+
+        assert_eq!(memo.len(), 81);
+        assert_eq!(memo.get(&10), Some(&55));
+        assert_eq!(memo.get(&81), None);
+    }
+
+    #[test]
+    fn indexed_priority_queue_ordering() {
+        let mut queue = IndexedPriorityQueue::new();
+        for (key, priority) in [('a', 5), ('b', 1), ('c', 8), ('d', 3), ('e', 7)] {
+            queue.push(key, priority);
+        }
+        assert_eq!(queue.len(), 5);
+
+        let mut popped = vec![];
+        while let Some((key, _)) = queue.pop_min() {
+            popped.push(key);
+        }
+        assert_eq!(popped, vec!['b', 'd', 'a', 'e', 'c']);
+        assert!(queue.is_empty());
+        assert_eq!(queue.pop_min(), None);
+    }
+
+    #[test]
+    fn indexed_priority_queue_decrease() {
+        let mut queue = IndexedPriorityQueue::new();
+        for (key, priority) in [("a", 5), ("b", 1), ("c", 8), ("d", 3)] {
+            queue.push(key, priority);
+        }
+
+        assert!(queue.decrease_priority(&"c", 0));
+        assert!(!queue.decrease_priority(&"a", 6));
+        assert!(!queue.decrease_priority(&"z", 0));
+        assert_eq!(queue.priority(&"c"), Some(&0));
+        assert_eq!(queue.priority(&"a"), Some(&5));
+
+        // pushing an existing key replaces its priority instead of adding a second entry
+        queue.push("b", 9);
+        assert_eq!(queue.len(), 4);
+
+        assert_eq!(queue.pop_min(), Some(("c", 0)));
+        assert_eq!(queue.pop_min(), Some(("d", 3)));
+        assert_eq!(queue.pop_min(), Some(("a", 5)));
+        assert!(!queue.contains(&"a"));
+        assert_eq!(queue.pop_min(), Some(("b", 9)));
+    }
+
+    #[test]
+    fn dijkstra_all_distances() {
+        let distances = dijkstra_all(NodeState { node: 0, cost: 0 }, next_nodes);
+
+        assert_eq!(distances.len(), 4);
+        assert_eq!(distances[&0], Reverse(0));
+        assert_eq!(distances[&1], Reverse(3));
+        assert_eq!(distances[&2], Reverse(1));
+        assert_eq!(distances[&3], Reverse(4));
+        assert_eq!(distances.get(&4), None);
+    }
+
+    #[test]
+    fn bfs_finds_fewest_steps() {
+        // from 1, each step can either add one or double
+        let result = bfs(1, |&n| [n + 1, n * 2], |&n| n == 10);
+        assert_eq!(result, Some((4, 10)));
+
+        let result = bfs(
+            1,
+            |&n: &u32| if n < 5 { vec![n + 1] } else { vec![] },
+            |&n| n == 10,
+        );
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn disjoint_set_path_compression() {
+        let mut sets = DisjointSet::new(6);
+        assert_eq!(sets.count(), 6);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+        assert_eq!(sets.count(), 3);
+
+        let root = sets.find(3);
+        for x in 0..4 {
+            assert_eq!(sets.find(x), root);
+            // after find, every member points directly at the root
+            assert_eq!(sets.parent[x], root);
+        }
+        assert_ne!(sets.find(4), root);
+        assert_ne!(sets.find(4), sets.find(5));
+
+        assert!(sets.union(4, 5));
+        assert!(sets.union(5, 0));
+        assert_eq!(sets.count(), 1);
+    }
+
+    #[test]
+    fn disjoint_set_map_unions() {
+        let mut sets = DisjointSetMap::default();
+        sets.union("a", "b");
+        sets.union("c", "d");
+        sets.insert("e");
+        assert_eq!(sets.count(), 3);
+        assert!(sets.same_set(&"a", &"b"));
+        assert!(!sets.same_set(&"a", &"c"));
+        assert!(!sets.same_set(&"a", &"z"));
+        assert_eq!(sets.find(&"z"), None);
+
+        sets.union("b", "d");
+        assert_eq!(sets.count(), 2);
+        assert!(sets.same_set(&"a", &"c"));
+    }
+
+    #[test]
+    fn topo_sort_breaks_ties_by_node_order() -> Result<()> {
+        // AoC 2018 day 7 sample
+        let nodes = ['A', 'B', 'C', 'D', 'E', 'F'];
+        let edges = [
+            ('C', 'A'),
+            ('C', 'F'),
+            ('A', 'B'),
+            ('A', 'D'),
+            ('B', 'E'),
+            ('D', 'E'),
+            ('F', 'E'),
+        ];
+
+        let sorted: String = topo_sort(&nodes, &edges)?.into_iter().collect();
+        assert_eq!(sorted, "CABDFE");
+
+        Ok(())
+    }
+
+    #[test]
+    fn topo_sort_cycle_errors() {
+        let nodes = [1, 2, 3, 4];
+        let edges = [(1, 2), (2, 3), (3, 2), (3, 4)];
+
+        assert!(topo_sort(&nodes, &edges).is_err());
+    }
+
+    #[test]
+    fn find_cycle_with_pre_period() {
+        // 0, 1, 2, 3, 4, 5, 6, 3, 4, 5, 6, ...
+        let step = |&n: &u32| if n < 6 { n + 1 } else { 3 };
+        assert_eq!(find_cycle(0, step), (3, 4));
+
+        // already in the cycle
+        assert_eq!(find_cycle(4, step), (0, 4));
+
+        // fixed point
+        assert_eq!(find_cycle(7, |_| 7), (0, 1));
+    }
+
+    #[test]
+    fn simulate_stops_early() {
+        // halves until it stops changing
+        let mut n = 100;
+        let steps = simulate(&mut n, 50, |n| {
+            let before = *n;
+            *n /= 2;
+            *n == before
+        });
+        assert_eq!(n, 0);
+        assert_eq!(steps, 8);
+    }
+
+    #[test]
+    fn simulate_hits_cap() {
+        let mut n = 0;
+        assert_eq!(
+            simulate(&mut n, 10, |n| {
+                *n += 1;
+                false
+            }),
+            10
+        );
+        assert_eq!(n, 10);
+
+        assert_eq!(simulate(&mut n, 0, |_| true), 0);
+    }
+}
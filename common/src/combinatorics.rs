@@ -0,0 +1,171 @@
+// owned subsets and orderings for brute force searches
+
+// every k-item subset, in lexicographic order of the item indices
+pub fn combinations<T: Clone>(items: &[T], k: usize) -> Vec<Vec<T>> {
+    let mut result = Vec::new();
+    let mut current = Vec::with_capacity(k);
+    combinations_from(items, k, &mut current, &mut result);
+    result
+}
+
+fn combinations_from<T: Clone>(
+    items: &[T],
+    k: usize,
+    current: &mut Vec<T>,
+    result: &mut Vec<Vec<T>>,
+) {
+    if current.len() == k {
+        result.push(current.clone());
+        return;
+    }
+
+    let needed = k - current.len();
+    // stop early once there aren't enough items left to fill the subset
+    for ix in 0..items.len().saturating_sub(needed - 1) {
+        current.push(items[ix].clone());
+        combinations_from(&items[ix + 1..], k, current, result);
+        current.pop();
+    }
+}
+
+// every ordering of items, in lexicographic order of the item indices
+pub fn permutations<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    let mut result = Vec::new();
+    let mut current = Vec::with_capacity(items.len());
+    let mut used = vec![false; items.len()];
+    permutations_from(items, &mut used, &mut current, &mut result);
+    result
+}
+
+fn permutations_from<T: Clone>(
+    items: &[T],
+    used: &mut [bool],
+    current: &mut Vec<T>,
+    result: &mut Vec<Vec<T>>,
+) {
+    if current.len() == items.len() {
+        result.push(current.clone());
+        return;
+    }
+
+    for ix in 0..items.len() {
+        if used[ix] {
+            continue;
+        }
+        used[ix] = true;
+        current.push(items[ix].clone());
+        permutations_from(items, used, current, result);
+        current.pop();
+        used[ix] = false;
+    }
+}
+
+// number of subsets (by position, so repeated values count separately) adding up to exactly target
+pub fn count_subsets_summing(items: &[u64], target: u64) -> usize {
+    let target = target as usize;
+    // ways[sum] is the number of subsets of the items seen so far adding up to sum
+    let mut ways = vec![0; target + 1];
+    ways[0] = 1;
+    for item in items {
+        let item = *item as usize;
+        // walking down so each item is used at most once
+        for sum in (item..=target).rev() {
+            ways[sum] += ways[sum - item];
+        }
+    }
+    ways[target]
+}
+
+// fewest items needed to add up to exactly target
+pub fn min_subset_size_summing(items: &[u64], target: u64) -> Option<usize> {
+    let target = target as usize;
+    let mut fewest: Vec<Option<usize>> = vec![None; target + 1];
+    fewest[0] = Some(0);
+    for item in items {
+        let item = *item as usize;
+        for sum in (item..=target).rev() {
+            if let Some(without) = fewest[sum - item] {
+                let with = without + 1;
+                fewest[sum] = Some(fewest[sum].map_or(with, |size| size.min(with)));
+            }
+        }
+    }
+    fewest[target]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combinations_order() {
+        assert_eq!(
+            combinations(&['a', 'b', 'c', 'd'], 2),
+            vec![
+                vec!['a', 'b'],
+                vec!['a', 'c'],
+                vec!['a', 'd'],
+                vec!['b', 'c'],
+                vec!['b', 'd'],
+                vec!['c', 'd'],
+            ]
+        );
+        assert_eq!(combinations(&[1, 2], 0), vec![Vec::<i32>::new()]);
+        assert!(combinations(&[1, 2], 3).is_empty());
+    }
+
+    #[test]
+    fn combinations_count() {
+        let items: Vec<usize> = (0..10).collect();
+        for (k, expected) in [(0, 1), (1, 10), (3, 120), (5, 252), (10, 1)] {
+            assert_eq!(combinations(&items, k).len(), expected);
+        }
+    }
+
+    #[test]
+    fn permutations_order() {
+        assert_eq!(
+            permutations(&[1, 2, 3]),
+            vec![
+                vec![1, 2, 3],
+                vec![1, 3, 2],
+                vec![2, 1, 3],
+                vec![2, 3, 1],
+                vec![3, 1, 2],
+                vec![3, 2, 1],
+            ]
+        );
+    }
+
+    #[test]
+    fn permutations_count() {
+        let items: Vec<usize> = (0..6).collect();
+        assert_eq!(permutations(&items).len(), 720);
+        assert_eq!(permutations(&items[..1]).len(), 1);
+        assert_eq!(permutations::<usize>(&[]), vec![Vec::<usize>::new()]);
+    }
+
+    const CONTAINERS: [u64; 5] = [20, 15, 10, 5, 5];
+
+    #[test]
+    fn subsets_summing_day17_sample() {
+        assert_eq!(count_subsets_summing(&CONTAINERS, 25), 4);
+        assert_eq!(min_subset_size_summing(&CONTAINERS, 25), Some(2));
+        assert_eq!(
+            combinations(&CONTAINERS, 2)
+                .iter()
+                .filter(|subset| subset.iter().sum::<u64>() == 25)
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn subsets_summing_edges() {
+        assert_eq!(count_subsets_summing(&CONTAINERS, 0), 1);
+        assert_eq!(min_subset_size_summing(&CONTAINERS, 0), Some(0));
+        assert_eq!(count_subsets_summing(&CONTAINERS, 100), 0);
+        assert_eq!(min_subset_size_summing(&CONTAINERS, 100), None);
+        assert_eq!(min_subset_size_summing(&CONTAINERS, 55), Some(5));
+    }
+}
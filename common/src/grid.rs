@@ -1,12 +1,14 @@
 use anyhow::*;
+use fnv::FnvHashMap;
 use itertools::Itertools;
 use std::cmp::{max, min, Eq, Ord, PartialEq, Reverse};
+use std::collections::{BinaryHeap, VecDeque};
 use std::fmt;
 use std::hash::{Hash, Hasher};
-use std::ops::{Index, IndexMut};
+use std::ops::{Add, Index, IndexMut, Sub};
 use std::str::FromStr;
 
-use crate::{dijkstra, wrap_parse_error, OptimizationState};
+use crate::{a_star, dijkstra, wrap_parse_error, IPoint2D, OptimizationState};
 
 // contains helpers for grids and unsigned points
 // coordinates are laid out like this
@@ -20,6 +22,7 @@ use crate::{dijkstra, wrap_parse_error, OptimizationState};
 // The * is at (x=4, y=3)
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point2D {
     pub x: usize,
     pub y: usize,
@@ -31,12 +34,14 @@ pub fn pt(x: usize, y: usize) -> Point2D {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bounds2D {
     pub width: usize,
     pub height: usize,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     Up,
     Left,
@@ -71,6 +76,130 @@ impl Direction {
             Left => Up,
         }
     }
+
+    pub fn counterclockwise90(self) -> Direction {
+        use Direction::*;
+        match self {
+            Up => Left,
+            Left => Down,
+            Down => Right,
+            Right => Up,
+        }
+    }
+
+    // (dx, dy) of a single step, remember that y = 0 is the top row
+    pub fn delta(self) -> (i32, i32) {
+        use Direction::*;
+        match self {
+            Up => (0, -1),
+            Down => (0, 1),
+            Left => (-1, 0),
+            Right => (1, 0),
+        }
+    }
+
+    pub fn from_char(c: char) -> Option<Direction> {
+        use Direction::*;
+        match c {
+            '^' | 'U' | 'u' => Some(Up),
+            'v' | 'D' | 'd' => Some(Down),
+            '<' | 'L' | 'l' => Some(Left),
+            '>' | 'R' | 'r' => Some(Right),
+            _ => None,
+        }
+    }
+
+    // case-insensitive, "forward" is treated as moving right like the submarine inputs
+    pub fn from_word(s: &str) -> Option<Direction> {
+        use Direction::*;
+        match s.to_ascii_lowercase().as_str() {
+            "up" => Some(Up),
+            "down" => Some(Down),
+            "left" => Some(Left),
+            "right" | "forward" => Some(Right),
+            _ => None,
+        }
+    }
+
+    // turns clockwise by amount quarter turns, negative amounts turn counterclockwise
+    pub fn turn(self, amount: i32) -> Direction {
+        match amount.rem_euclid(4) {
+            0 => self,
+            1 => self.clockwise90(),
+            2 => self.opposite(),
+            _ => self.counterclockwise90(),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let word = match self {
+            Direction::Up => "up",
+            Direction::Down => "down",
+            Direction::Left => "left",
+            Direction::Right => "right",
+        };
+        write!(f, "{}", word)
+    }
+}
+
+// cardinal directions plus diagonals, for when diagonal movement is allowed
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+// clockwise, starting from North
+pub const DIRECTIONS8: [Direction8; 8] = [
+    Direction8::North,
+    Direction8::NorthEast,
+    Direction8::East,
+    Direction8::SouthEast,
+    Direction8::South,
+    Direction8::SouthWest,
+    Direction8::West,
+    Direction8::NorthWest,
+];
+
+impl Direction8 {
+    // (dx, dy) of a single step, North is towards y = 0
+    pub fn delta(self) -> (i32, i32) {
+        use Direction8::*;
+        match self {
+            North => (0, -1),
+            NorthEast => (1, -1),
+            East => (1, 0),
+            SouthEast => (1, 1),
+            South => (0, 1),
+            SouthWest => (-1, 1),
+            West => (-1, 0),
+            NorthWest => (-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        use Direction8::*;
+        matches!(self, NorthEast | SouthEast | SouthWest | NorthWest)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        match dir {
+            Direction::Up => Direction8::North,
+            Direction::Right => Direction8::East,
+            Direction::Down => Direction8::South,
+            Direction::Left => Direction8::West,
+        }
+    }
 }
 
 impl Point2D {
@@ -81,6 +210,11 @@ impl Point2D {
         self.x + self.y * width
     }
 
+    // inverse of index
+    pub fn from_index(index: usize, width: usize) -> Point2D {
+        pt(index % width, index / width)
+    }
+
     fn bounded_relatives<T>(&self, bounds: Bounds2D, deltas: T) -> impl Iterator<Item = Point2D>
     where
         T: IntoIterator<Item = (i32, i32)>,
@@ -98,23 +232,33 @@ impl Point2D {
     }
 
     pub fn cardinal_neighbors(&self, bounds: Bounds2D) -> impl Iterator<Item = Point2D> {
-        self.bounded_relatives(bounds, [(-1, 0), (1, 0), (0, -1), (0, 1)])
+        self.adjacent(bounds, false)
     }
 
     pub fn neighbors(&self, bounds: Bounds2D) -> impl Iterator<Item = Point2D> {
-        self.bounded_relatives(
-            bounds,
-            [
-                (-1, -1),
-                (-1, 0),
-                (-1, 1),
-                (0, -1),
-                (0, 1),
-                (1, -1),
-                (1, 0),
-                (1, 1),
-            ],
-        )
+        self.adjacent(bounds, true)
+    }
+
+    // cardinal neighbors, plus diagonals when diagonal is set
+    pub fn adjacent(&self, bounds: Bounds2D, diagonal: bool) -> impl Iterator<Item = Point2D> {
+        const CARDINAL_DELTAS: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+        const ALL_DELTAS: [(i32, i32); 8] = [
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+            (0, -1),
+            (0, 1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+        ];
+
+        let deltas: &'static [(i32, i32)] = if diagonal {
+            &ALL_DELTAS
+        } else {
+            &CARDINAL_DELTAS
+        };
+        self.bounded_relatives(bounds, deltas.iter().copied())
     }
 
     pub fn left(&self) -> Option<Point2D> {
@@ -170,6 +314,26 @@ impl Point2D {
         max(self.x, other.x) - min(self.x, other.x) + max(self.y, other.y) - min(self.y, other.y)
     }
 
+    // steps needed when diagonal moves are allowed
+    pub fn chebyshev_distance(&self, other: Point2D) -> usize {
+        max(
+            self.horizontal_distance(other),
+            self.vertical_distance(other),
+        )
+    }
+
+    // every in-bounds point within manhattan distance r, not including self
+    pub fn within_manhattan(&self, r: usize, bounds: Bounds2D) -> impl Iterator<Item = Point2D> {
+        let center = *self;
+        let max_x = min(self.x + r, bounds.width.saturating_sub(1));
+        let max_y = min(self.y + r, bounds.height.saturating_sub(1));
+
+        (self.y.saturating_sub(r)..=max_y)
+            .cartesian_product(self.x.saturating_sub(r)..=max_x)
+            .map(|(y, x)| pt(x, y))
+            .filter(move |p| *p != center && p.manhattan_distance(center) <= r)
+    }
+
     pub fn vertical_distance(&self, other: Point2D) -> usize {
         max(self.y, other.y) - min(self.y, other.y)
     }
@@ -189,6 +353,43 @@ impl Point2D {
             .map(|(x, y)| pt(x, y))
     }
 
+    // Bresenham's line algorithm, includes both ends. Lines are always traced from the lesser
+    // point so that a.line_to(b) is b.line_to(a) reversed
+    pub fn line_to(&self, other: &Point2D) -> Vec<Point2D> {
+        if self > other {
+            let mut line = other.line_to(self);
+            line.reverse();
+            return line;
+        }
+
+        let (x1, y1) = (other.x as i64, other.y as i64);
+        let (mut x, mut y) = (self.x as i64, self.y as i64);
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let step_x = if x < x1 { 1 } else { -1 };
+        let step_y = if y < y1 { 1 } else { -1 };
+        let mut error = dx + dy;
+
+        let mut line = vec![];
+        loop {
+            line.push(pt(x as usize, y as usize));
+            if x == x1 && y == y1 {
+                break;
+            }
+            let error2 = error * 2;
+            if error2 >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if error2 <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+
+        line
+    }
+
     pub fn mv(&self, dir: Direction, bounds: Bounds2D) -> Option<Point2D> {
         match dir {
             Direction::Up => self.up(),
@@ -198,6 +399,26 @@ impl Point2D {
         }
     }
 
+    // successive points in a direction, not including self, until leaving bounds
+    pub fn ray(&self, dir: Direction, bounds: Bounds2D) -> impl Iterator<Item = Point2D> {
+        std::iter::successors(self.mv(dir, bounds), move |pt| pt.mv(dir, bounds))
+    }
+
+    // moving off one edge comes back on the opposite edge
+    pub fn wrapping_move(&self, dir: Direction, bounds: Bounds2D) -> Point2D {
+        match dir {
+            Direction::Up => pt(self.x, (self.y + bounds.height - 1) % bounds.height),
+            Direction::Left => pt((self.x + bounds.width - 1) % bounds.width, self.y),
+            Direction::Down => pt(self.x, (self.y + 1) % bounds.height),
+            Direction::Right => pt((self.x + 1) % bounds.width, self.y),
+        }
+    }
+
+    pub fn move_ordinal(&self, dir: Direction8, bounds: Bounds2D) -> Option<Point2D> {
+        let (dx, dy) = dir.delta();
+        self.move_by_delta(dx, dy, bounds)
+    }
+
     pub fn move_by(&self, dir: Direction, distance: usize, bounds: Bounds2D) -> Option<Point2D> {
         match dir {
             Direction::Up if self.y >= distance => Some(pt(self.x, self.y - distance)),
@@ -213,16 +434,15 @@ impl Point2D {
     }
 
     pub fn move_by_delta(&self, dx: i32, dy: i32, bounds: Bounds2D) -> Option<Point2D> {
-        let new_x = self.x as i32 + dx;
-        let new_y = self.y as i32 + dy;
+        self.translate(dx, dy).filter(|pt| bounds.contains(pt))
+    }
 
-        if new_x < 0 || new_x >= bounds.width as i32 {
-            return None;
-        }
-        if new_y < 0 || new_y >= bounds.height as i32 {
-            return None;
-        }
-        Some(pt(new_x as usize, new_y as usize))
+    // like move_by_delta, but only checks for moving below zero
+    pub fn translate(&self, dx: i32, dy: i32) -> Option<Point2D> {
+        Some(pt(
+            self.x.checked_add_signed(dx as isize)?,
+            self.y.checked_add_signed(dy as isize)?,
+        ))
     }
 
     pub fn direction_to(&self, other: &Point2D) -> Option<Direction> {
@@ -240,6 +460,24 @@ impl Point2D {
     }
 }
 
+impl Add<(i32, i32)> for Point2D {
+    type Output = Point2D;
+
+    fn add(self, (dx, dy): (i32, i32)) -> Point2D {
+        self.translate(dx, dy)
+            .unwrap_or_else(|| panic!("moving {self} by ({dx},{dy}) went below zero"))
+    }
+}
+
+// subtracting points gives the signed delta that would move rhs to self
+impl Sub for Point2D {
+    type Output = (i32, i32);
+
+    fn sub(self, rhs: Point2D) -> (i32, i32) {
+        (self.x as i32 - rhs.x as i32, self.y as i32 - rhs.y as i32)
+    }
+}
+
 impl FromStr for Point2D {
     type Err = Error;
 
@@ -298,6 +536,35 @@ impl Bounds2D {
         pt(self.width - 1, self.height - 1)
     }
 
+    // rounds down (towards the origin) when a dimension is even, empty bounds have no center
+    pub fn center(&self) -> Option<Point2D> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(pt((self.width - 1) / 2, (self.height - 1) / 2))
+        }
+    }
+
+    // outer ring of points, clockwise starting at the origin, each point only once
+    pub fn iter_border(&self) -> impl Iterator<Item = Point2D> {
+        let (width, height) = if self.is_empty() {
+            (0, 0)
+        } else {
+            (self.width, self.height)
+        };
+        let right = width.saturating_sub(1);
+        let bottom = height.saturating_sub(1);
+        // a single row or column is covered entirely by the top and right edges
+        let bottom_len = if height > 1 { right } else { 0 };
+        let left_end = if width > 1 { bottom } else { 1 };
+
+        (0..width)
+            .map(|x| pt(x, 0))
+            .chain((1..height).map(move |y| pt(right, y)))
+            .chain((0..bottom_len).rev().map(move |x| pt(x, bottom)))
+            .chain((1..left_end).rev().map(|y| pt(0, y)))
+    }
+
     pub fn len(&self) -> usize {
         self.width * self.height
     }
@@ -319,9 +586,40 @@ impl Bounds2D {
     pub fn contains(&self, pt: &Point2D) -> bool {
         pt.x < self.width && pt.y < self.height
     }
+
+    // 0 = top left, 1 = top right, 2 = bottom left, 3 = bottom right. on odd sized bounds the
+    // middle row and column don't belong to any quadrant, and neither does anything out of bounds
+    pub fn quadrant(&self, pt: Point2D) -> Option<u8> {
+        if !self.contains(&pt) {
+            return None;
+        }
+        let side = |value: usize, len: usize| {
+            if value < len / 2 {
+                Some(0)
+            } else if value >= len.div_ceil(2) {
+                Some(1)
+            } else {
+                None
+            }
+        };
+        Some(side(pt.y, self.height)? * 2 + side(pt.x, self.width)?)
+    }
+}
+
+// how many of the points land in each quadrant, indexed like Bounds2D::quadrant
+pub fn count_quadrants<I>(bounds: Bounds2D, points: I) -> [usize; 4]
+where
+    I: IntoIterator<Item = Point2D>,
+{
+    let mut counts = [0; 4];
+    for quadrant in points.into_iter().filter_map(|pt| bounds.quadrant(pt)) {
+        counts[quadrant as usize] += 1;
+    }
+    counts
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rect {
     origin: Point2D,
     terminex: Point2D,
@@ -341,9 +639,53 @@ impl Rect {
             && self.origin.y <= pt.y
             && pt.y <= self.terminex.y
     }
+
+    // rects are inclusive, so rects sharing an edge intersect along that edge
+    pub fn intersect(&self, other: &Rect) -> Option<Rect> {
+        let origin = pt(
+            max(self.origin.x, other.origin.x),
+            max(self.origin.y, other.origin.y),
+        );
+        let terminex = pt(
+            min(self.terminex.x, other.terminex.x),
+            min(self.terminex.y, other.terminex.y),
+        );
+
+        if origin.x > terminex.x || origin.y > terminex.y {
+            None
+        } else {
+            Some(Rect { origin, terminex })
+        }
+    }
+
+    // smallest rect containing both
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            origin: pt(
+                min(self.origin.x, other.origin.x),
+                min(self.origin.y, other.origin.y),
+            ),
+            terminex: pt(
+                max(self.terminex.x, other.terminex.x),
+                max(self.terminex.y, other.terminex.y),
+            ),
+        }
+    }
+
+    pub fn area(&self) -> usize {
+        (self.terminex.x - self.origin.x + 1) * (self.terminex.y - self.origin.y + 1)
+    }
+
+    // reading order (left to right, then top to bottom)
+    pub fn iter(&self) -> impl Iterator<Item = Point2D> {
+        (self.origin.y..=self.terminex.y)
+            .cartesian_product(self.origin.x..=self.terminex.x)
+            .map(|(y, x)| pt(x, y))
+    }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Grid2D<T> {
     pub data: Vec<Vec<T>>,
     pub bounds: Bounds2D,
@@ -373,6 +715,27 @@ impl<T> Grid2D<T> {
             .map(move |col| (0..self.bounds.height).map(move |row| &self.data[row][col]))
     }
 
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        self.data.iter_mut().map(|row| row.as_mut_slice())
+    }
+
+    // columns aren't contiguous so there's no cols_mut, f gets the row number and the cell
+    pub fn map_col<F>(&mut self, col: usize, mut f: F)
+    where
+        F: FnMut(usize, &mut T),
+    {
+        if col >= self.bounds.width {
+            panic!(
+                "invalid column number {col}, grid width is {}",
+                self.bounds.width
+            );
+        }
+
+        for (y, row) in self.data.iter_mut().enumerate() {
+            f(y, &mut row[col]);
+        }
+    }
+
     pub fn enumerate_rows(&self) -> impl Iterator<Item = (usize, impl Iterator<Item = &T>)> {
         self.rows().enumerate()
     }
@@ -393,16 +756,91 @@ impl<T> Grid2D<T> {
             .map(|pt| (pt, &self.data[pt.y][pt.x]))
     }
 
+    // owned version of iter_horizontal, for when the grid isn't needed afterwards
+    pub fn into_cells(self) -> impl Iterator<Item = (Point2D, T)> {
+        self.data.into_iter().enumerate().flat_map(|(y, row)| {
+            row.into_iter()
+                .enumerate()
+                .map(move |(x, value)| (pt(x, y), value))
+        })
+    }
+
     pub fn cardinal_neighbors(&self, pt: Point2D) -> impl Iterator<Item = (Point2D, &T)> {
-        pt.cardinal_neighbors(self.bounds)
+        self.adjacent(pt, false)
+    }
+
+    // 4 neighbors, or 8 with diagonal set
+    pub fn adjacent(&self, pt: Point2D, diagonal: bool) -> impl Iterator<Item = (Point2D, &T)> {
+        pt.adjacent(self.bounds, diagonal)
             .map(|pt| (pt, &self.data[pt.y][pt.x]))
     }
 
-    pub fn neighbors(&self, pt: Point2D) -> impl Iterator<Item = (Point2D, &T)> {
-        pt.neighbors(self.bounds)
+    // how many of the (up to 8) neighbors match pred
+    pub fn count_neighbors<F>(&self, pt: Point2D, pred: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.adjacent(pt, true).filter(|(_, v)| pred(v)).count()
+    }
+
+    pub fn count_cardinal_neighbors<F>(&self, pt: Point2D, pred: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.adjacent(pt, false).filter(|(_, v)| pred(v)).count()
+    }
+
+    // always 4 neighbors, wrapping around the edges, in CARDINAL_DIRECTIONS order
+    pub fn wrapping_cardinal_neighbors(&self, pt: Point2D) -> impl Iterator<Item = (Point2D, &T)> {
+        let bounds = self.bounds;
+        CARDINAL_DIRECTIONS
+            .into_iter()
+            .map(move |dir| pt.wrapping_move(dir, bounds))
+            .map(|pt| (pt, &self.data[pt.y][pt.x]))
+    }
+
+    // outward clockwise spiral (right, down, left, up with legs of 1, 1, 2, 2, 3, 3...) from
+    // start, skipping anything out of bounds until every cell has been visited
+    pub fn iter_spiral(&self, start: Point2D) -> impl Iterator<Item = (Point2D, &T)> {
+        const DELTAS: [(i64, i64); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+        let (mut x, mut y) = (start.x as i64, start.y as i64);
+        let mut dir = 0;
+        let mut leg_len = 1;
+        let mut leg_pos = 0;
+        let mut first = true;
+        let spiral = std::iter::from_fn(move || {
+            if first {
+                first = false;
+            } else {
+                let (dx, dy) = DELTAS[dir];
+                x += dx;
+                y += dy;
+                leg_pos += 1;
+                if leg_pos == leg_len {
+                    leg_pos = 0;
+                    dir = (dir + 1) % 4;
+                    if dir % 2 == 0 {
+                        leg_len += 1;
+                    }
+                }
+            }
+            Some((x, y))
+        });
+
+        let width = self.bounds.width as i64;
+        let height = self.bounds.height as i64;
+        spiral
+            .filter(move |(x, y)| *x >= 0 && *x < width && *y >= 0 && *y < height)
+            .take(self.bounds.len())
+            .map(|(x, y)| pt(x as usize, y as usize))
             .map(|pt| (pt, &self.data[pt.y][pt.x]))
     }
 
+    pub fn neighbors(&self, pt: Point2D) -> impl Iterator<Item = (Point2D, &T)> {
+        self.adjacent(pt, true)
+    }
+
     pub fn cardinal_neighbor(&self, pt: Point2D, dir: Direction) -> Option<(Point2D, &T)> {
         pt.cardinal_neighbor(dir, self.bounds)
             .map(|pt| (pt, &self.data[pt.y][pt.x]))
@@ -417,6 +855,99 @@ impl<T> Grid2D<T> {
         });
     }
 
+    // like transform, but only visits cells inside rect (clipped to the grid)
+    pub fn transform_region<F>(&mut self, rect: Rect, mut f: F)
+    where
+        F: FnMut((Point2D, &T)) -> T,
+    {
+        if self.bounds.width == 0 || self.bounds.height == 0 {
+            return;
+        }
+        let full = Rect::new(pt(0, 0), pt(self.bounds.width - 1, self.bounds.height - 1));
+        if let Some(clipped) = rect.intersect(&full) {
+            clipped.iter().for_each(|pt| {
+                self[pt] = f((pt, &self[pt]));
+            });
+        }
+    }
+
+    pub fn swap(&mut self, a: Point2D, b: Point2D) {
+        if !self.bounds.contains(&a) || !self.bounds.contains(&b) {
+            panic!("cannot swap {a} and {b}, grid is {:?}", self.bounds);
+        }
+
+        if a.y == b.y {
+            self.data[a.y].swap(a.x, b.x);
+        } else {
+            let (upper, lower) = if a.y < b.y { (a, b) } else { (b, a) };
+            let (top, bottom) = self.data.split_at_mut(lower.y);
+            std::mem::swap(&mut top[upper.y][upper.x], &mut bottom[0][lower.x]);
+        }
+    }
+
+    pub fn set_row<I>(&mut self, row: usize, values: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        if row >= self.bounds.height {
+            panic!(
+                "invalid row number {row}, grid height is {}",
+                self.bounds.height
+            );
+        }
+        let values: Vec<T> = values.into_iter().collect();
+        if values.len() != self.bounds.width {
+            panic!(
+                "row has {} values, but grid width is {}",
+                values.len(),
+                self.bounds.width
+            );
+        }
+
+        self.data[row] = values;
+    }
+
+    pub fn set_col<I>(&mut self, col: usize, values: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        if col >= self.bounds.width {
+            panic!(
+                "invalid column number {col}, grid width is {}",
+                self.bounds.width
+            );
+        }
+        let values: Vec<T> = values.into_iter().collect();
+        if values.len() != self.bounds.height {
+            panic!(
+                "column has {} values, but grid height is {}",
+                values.len(),
+                self.bounds.height
+            );
+        }
+
+        for (row, value) in self.data.iter_mut().zip(values) {
+            row[col] = value;
+        }
+    }
+
+    pub fn iter_horizontal_mut(&mut self) -> impl Iterator<Item = (Point2D, &mut T)> {
+        self.data.iter_mut().enumerate().flat_map(|(y, row)| {
+            row.iter_mut()
+                .enumerate()
+                .map(move |(x, value)| (pt(x, y), value))
+        })
+    }
+
+    // like transform, but mutates each cell in place instead of replacing it
+    pub fn map_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Point2D, &mut T),
+    {
+        self.iter_horizontal_mut()
+            .for_each(|(pt, value)| f(pt, value));
+    }
+
     pub fn transform_neighbors<F>(&mut self, pt: Point2D, mut f: F)
     where
         F: FnMut((Point2D, &T)) -> T,
@@ -438,6 +969,25 @@ impl<T> Grid2D<T> {
         &self.data[pt.y][pt.x]
     }
 
+    // top-left, top-right, bottom-left, bottom-right
+    pub fn corner_values(&self) -> [&T; 4] {
+        let right = self.bounds.width - 1;
+        let bottom = self.bounds.height - 1;
+        [
+            &self.data[0][0],
+            &self.data[0][right],
+            &self.data[bottom][0],
+            &self.data[bottom][right],
+        ]
+    }
+
+    // None for negative or out of bounds coordinates instead of panicking
+    pub fn get_signed(&self, pt: IPoint2D) -> Option<&T> {
+        let x = usize::try_from(pt.x).ok()?;
+        let y = usize::try_from(pt.y).ok()?;
+        self.data.get(y)?.get(x)
+    }
+
     pub fn row(&self, row: usize) -> impl Iterator<Item = (Point2D, &T)> {
         if row >= self.bounds.height {
             panic!("invalid row number");
@@ -454,6 +1004,61 @@ impl<T> Grid2D<T> {
         (0..self.bounds.height).map(move |row| (pt(col, row), &self.data[row][col]))
     }
 
+    // every point where the grids differ, in reading order. Panics if the bounds don't match
+    pub fn diff<'a>(&'a self, other: &'a Grid2D<T>) -> Vec<(Point2D, &'a T, &'a T)>
+    where
+        T: PartialEq,
+    {
+        if self.bounds != other.bounds {
+            panic!(
+                "cannot diff a {}x{} grid with a {}x{} grid",
+                self.bounds.width, self.bounds.height, other.bounds.width, other.bounds.height
+            );
+        }
+
+        self.iter_horizontal()
+            .map(|(pt, value)| (pt, value, &other[pt]))
+            .filter(|(_, a, b)| a != b)
+            .collect()
+    }
+
+    pub fn count_where<F>(&self, pred: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.data.iter().flatten().filter(|x| pred(x)).count()
+    }
+
+    // number of cells off the loop that are inside it, by casting a ray left to right along each
+    // row and flipping inside/outside every time it crosses the loop
+    //
+    // connects_up is only asked about loop cells, and must be true for the ones joined to the loop
+    // cell directly above them (|, L and J in 2023 day 10). only those count as crossings, so a
+    // run like L--7 flips once while L--J or F--7 doesn't flip at all, and cells squeezed between
+    // two pipes stay outside. being on the loop isn't enough to tell, since the loop can run
+    // right alongside itself
+    pub fn count_enclosed<F, C>(&self, on_loop: F, connects_up: C) -> usize
+    where
+        F: Fn(Point2D, &T) -> bool,
+        C: Fn(Point2D, &T) -> bool,
+    {
+        let mut enclosed = 0;
+        for (y, row) in self.data.iter().enumerate() {
+            let mut inside = false;
+            for (x, value) in row.iter().enumerate() {
+                let pt = pt(x, y);
+                if on_loop(pt, value) {
+                    if connects_up(pt, value) {
+                        inside = !inside;
+                    }
+                } else if inside {
+                    enclosed += 1;
+                }
+            }
+        }
+        enclosed
+    }
+
     pub fn map<F, U>(&self, map_fn: F) -> Grid2D<U>
     where
         F: Fn((Point2D, &T)) -> U,
@@ -473,6 +1078,72 @@ impl<T> Grid2D<T> {
                 .collect(),
         }
     }
+
+    // steps from each cell to the nearest source, moving cardinally through passable cells.
+    // Sources are always distance 0, cells that can't be reached are None
+    pub fn distance_field<F>(&self, sources: &[Point2D], passable: F) -> Grid2D<Option<usize>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut distances = Grid2D::new_constant(self.bounds, None);
+        let mut todo = VecDeque::new();
+        for source in sources {
+            if distances[*source].is_none() {
+                distances[*source] = Some(0);
+                todo.push_back(*source);
+            }
+        }
+
+        while let Some(current) = todo.pop_front() {
+            let distance = distances[current].unwrap_or_default();
+            for (next, value) in self.cardinal_neighbors(current) {
+                if distances[next].is_none() && passable(value) {
+                    distances[next] = Some(distance + 1);
+                    todo.push_back(next);
+                }
+            }
+        }
+
+        distances
+    }
+
+    // stamps other onto this grid with its origin at `at`, anything that doesn't fit is dropped
+    pub fn overlay<F>(&mut self, other: &Grid2D<T>, at: Point2D, combine: F)
+    where
+        F: Fn(&T, &T) -> T,
+    {
+        for (other_pt, incoming) in other.iter_horizontal() {
+            let target = pt(at.x + other_pt.x, at.y + other_pt.y);
+            if self.bounds.contains(&target) {
+                self[target] = combine(&self[target], incoming);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T> Grid2D<T>
+where
+    T: Send + Sync,
+{
+    // parallel version of transform, rows are computed on the rayon thread pool
+    //
+    // NOTE: unlike transform, every call to f sees the grid as it was before this call, since
+    // the new values are built in a separate buffer
+    pub fn par_transform<F>(&mut self, f: F)
+    where
+        F: Fn((Point2D, &T)) -> T + Sync,
+    {
+        use rayon::prelude::*;
+
+        let width = self.bounds.width;
+        let current = &self.data;
+        let data: Vec<Vec<T>> = (0..self.bounds.height)
+            .into_par_iter()
+            .map(|y| (0..width).map(|x| f((pt(x, y), &current[y][x]))).collect())
+            .collect();
+        self.data = data;
+    }
 }
 
 // basically a reverse sorter for T, with the location along for the ride
@@ -504,10 +1175,15 @@ where
 {
     // Dijkstra’s algorithm
     pub fn shortest_path(&self) -> T {
+        self.shortest_path_between(Point2D::ORIGIN, self.bounds.bottom_right())
+            .unwrap_or_default()
+    }
+
+    pub fn shortest_path_between(&self, start: Point2D, end: Point2D) -> Option<T> {
         dijkstra(
             ShortestPathState {
                 distance: Default::default(),
-                pt: Point2D::ORIGIN,
+                pt: start,
             },
             |&ShortestPathState { distance, pt }| {
                 self.cardinal_neighbors(pt)
@@ -516,22 +1192,161 @@ where
                         pt,
                     })
             },
-            |ShortestPathState { distance: _, pt }| *pt == self.bounds.bottom_right(),
+            |ShortestPathState { distance: _, pt }| *pt == end,
         )
         .map(|state| state.distance)
-        .unwrap_or_default()
     }
-}
 
-impl<T> Grid2D<T>
-where
-    T: Copy,
-{
-    pub fn new_constant(bounds: Bounds2D, value: T) -> Grid2D<T> {
+    // same as shortest_path_between, but also returns the points along the way (including start
+    // and end)
+    pub fn shortest_path_route(&self, start: Point2D, end: Point2D) -> Option<(T, Vec<Point2D>)> {
+        // best known distance to each point, along with the point we came from
+        let mut best: FnvHashMap<Point2D, (T, Point2D)> = FnvHashMap::default();
+        let mut heap = BinaryHeap::new();
+        best.insert(start, (Default::default(), start));
+        heap.push(Reverse((T::default(), start)));
+
+        while let Some(Reverse((distance, current))) = heap.pop() {
+            if current == end {
+                let mut route = vec![current];
+                let mut current = current;
+                while current != start {
+                    current = best[&current].1;
+                    route.push(current);
+                }
+                route.reverse();
+                return Some((distance, route));
+            }
+
+            if distance > best[&current].0 {
+                continue;
+            }
+
+            for (next, cost) in self.cardinal_neighbors(current) {
+                let next_distance = distance + *cost;
+                match best.get(&next) {
+                    Some((prev_distance, _)) if next_distance >= *prev_distance => (),
+                    _ => {
+                        best.insert(next, (next_distance, current));
+                        heap.push(Reverse((next_distance, next)));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    // A* using manhattan distance scaled by the cheapest cell as the heuristic, which never
+    // overestimates so it returns the same answer as shortest_path
+    pub fn shortest_path_astar(&self) -> T {
+        let end = self.bounds.bottom_right();
+        let min_cost = match self.data.iter().flatten().min() {
+            Some(min_cost) => *min_cost,
+            None => return Default::default(),
+        };
+
+        // only Add is available on T, so build up min_cost * distance ahead of time
+        let mut scaled_costs: Vec<T> = vec![Default::default()];
+        for ix in 0..self.bounds.width + self.bounds.height {
+            scaled_costs.push(scaled_costs[ix] + min_cost);
+        }
+
+        a_star(
+            AStarShortestPathState {
+                distance: Default::default(),
+                pt: Point2D::ORIGIN,
+            },
+            |&AStarShortestPathState { distance, pt }| {
+                self.cardinal_neighbors(pt)
+                    .map(move |(pt, dist_there)| AStarShortestPathState {
+                        distance: distance + *dist_there,
+                        pt,
+                    })
+            },
+            |AStarShortestPathState { distance: _, pt }| scaled_costs[pt.manhattan_distance(end)],
+            |AStarShortestPathState { distance: _, pt }| *pt == end,
+        )
+        .map(|state| state.distance)
+        .unwrap_or_default()
+    }
+}
+
+impl<T> Grid2D<T> {
+    // Dijkstra's algorithm over cardinal moves, where cost(from, to) gives the cost of stepping
+    // from one cell to its neighbor, or None if that move isn't allowed (walls, cliffs, etc)
+    pub fn pathfind<C>(&self, start: Point2D, goal: Point2D, cost: C) -> Option<u64>
+    where
+        C: Fn(Point2D, Point2D) -> Option<u64>,
+    {
+        let bounds = self.bounds;
+        dijkstra(
+            ShortestPathState {
+                distance: 0,
+                pt: start,
+            },
+            |&ShortestPathState { distance, pt }| {
+                let cost = &cost;
+                pt.cardinal_neighbors(bounds).filter_map(move |next| {
+                    cost(pt, next).map(|step| ShortestPathState {
+                        distance: distance + step,
+                        pt: next,
+                    })
+                })
+            },
+            |ShortestPathState { distance: _, pt }| *pt == goal,
+        )
+        .map(|state| state.distance)
+    }
+}
+
+// a_star scores must not be reversed, so this can't share ShortestPathState
+#[derive(Copy, Clone, Eq, PartialEq)]
+struct AStarShortestPathState<T> {
+    distance: T,
+    pt: Point2D,
+}
+
+impl<T> OptimizationState for AStarShortestPathState<T>
+where
+    T: Copy + Ord,
+{
+    type CacheKey = Point2D;
+    type Score = T;
+
+    fn cache_key(&self) -> Point2D {
+        self.pt
+    }
+
+    fn score(&self) -> T {
+        self.distance
+    }
+}
+
+impl<T> Grid2D<T>
+where
+    T: Copy,
+{
+    pub fn new_constant(bounds: Bounds2D, value: T) -> Grid2D<T> {
         let data: Vec<Vec<T>> = vec![vec![value; bounds.width]; bounds.height];
         Grid2D { data, bounds }
     }
 
+    // dense grid of default with the given points written over it. points outside bounds are
+    // skipped, and later points win when one shows up twice
+    pub fn from_points<I>(points: I, bounds: Bounds2D, default: T) -> Grid2D<T>
+    where
+        I: IntoIterator<Item = (Point2D, T)>,
+    {
+        let mut grid = Grid2D::new_constant(bounds, default);
+        for (pt, value) in points {
+            if bounds.contains(&pt) {
+                grid[pt] = value;
+            }
+        }
+        grid
+    }
+
     pub fn insert_row(&mut self, row: usize, value: T) {
         self.data.insert(row, vec![value; self.bounds.width]);
         self.bounds.height += 1;
@@ -544,6 +1359,35 @@ where
         self.bounds.width += 1;
     }
 
+    // surrounds the grid with thickness cells of fill on every side
+    pub fn with_border(&self, thickness: usize, fill: T) -> Grid2D<T> {
+        let bounds = Bounds2D {
+            width: self.bounds.width + thickness * 2,
+            height: self.bounds.height + thickness * 2,
+        };
+        let mut grid = Grid2D::new_constant(bounds, fill);
+        for (y, row) in self.data.iter().enumerate() {
+            grid.data[y + thickness][thickness..thickness + self.bounds.width].copy_from_slice(row);
+        }
+        grid
+    }
+
+    // inverse of with_border, drops thickness cells from every side
+    pub fn crop_border(&self, thickness: usize) -> Grid2D<T> {
+        if thickness * 2 > self.bounds.width || thickness * 2 > self.bounds.height {
+            panic!("border is thicker than the grid");
+        }
+        let bounds = Bounds2D {
+            width: self.bounds.width - thickness * 2,
+            height: self.bounds.height - thickness * 2,
+        };
+        let data = self.data[thickness..thickness + bounds.height]
+            .iter()
+            .map(|row| row[thickness..thickness + bounds.width].to_vec())
+            .collect();
+        Grid2D { data, bounds }
+    }
+
     pub fn rotate90(&self) -> Grid2D<T> {
         let bounds = Bounds2D {
             width: self.bounds.height,
@@ -561,6 +1405,48 @@ where
 
         Grid2D { data, bounds }
     }
+
+    // the 4 rotations (clockwise, starting with self unchanged) followed by the same 4 mirrored
+    // left to right. covers every way a tile can be placed in a jigsaw
+    pub fn orientations(&self) -> [Grid2D<T>; 8] {
+        let r0 = self.clone();
+        let r1 = r0.rotate90();
+        let r2 = r1.rotate90();
+        let r3 = r2.rotate90();
+        let mirror = |grid: &Grid2D<T>| Grid2D {
+            data: grid
+                .data
+                .iter()
+                .map(|row| row.iter().rev().copied().collect())
+                .collect(),
+            bounds: grid.bounds,
+        };
+        let (m0, m1, m2, m3) = (mirror(&r0), mirror(&r1), mirror(&r2), mirror(&r3));
+        [r0, r1, r2, r3, m0, m1, m2, m3]
+    }
+
+    // top, right, bottom and left borders, all in reading order (left to right or top to
+    // bottom), so matching neighbors compare equal without reversing
+    pub fn edges(&self) -> [Vec<T>; 4] {
+        let last_x = self.bounds.width - 1;
+        let last_y = self.bounds.height - 1;
+        [
+            self.data[0].clone(),
+            self.data.iter().map(|row| row[last_x]).collect(),
+            self.data[last_y].clone(),
+            self.data.iter().map(|row| row[0]).collect(),
+        ]
+    }
+}
+
+impl<T> Grid2D<T>
+where
+    T: Default + Clone,
+{
+    pub fn new_default(bounds: Bounds2D) -> Grid2D<T> {
+        let data: Vec<Vec<T>> = vec![vec![T::default(); bounds.width]; bounds.height];
+        Grid2D { data, bounds }
+    }
 }
 
 impl<T> Grid2D<T>
@@ -580,6 +1466,294 @@ where
             },
         }
     }
+
+    pub fn grow_x(&self, by: usize, fill: T) -> Grid2D<T> {
+        let mut data: Vec<Vec<T>> = self.data.clone();
+        for row in data.iter_mut() {
+            row.resize(self.bounds.width + by, fill);
+        }
+        Grid2D {
+            data,
+            bounds: Bounds2D {
+                width: self.bounds.width + by,
+                height: self.bounds.height,
+            },
+        }
+    }
+
+    // grows the shorter side (to the right or bottom) until width == height
+    pub fn pad_to_square(&self, fill: T) -> Grid2D<T> {
+        let Bounds2D { width, height } = self.bounds;
+        if width < height {
+            self.grow_x(height - width, fill)
+        } else {
+            self.grow_y(width - height, fill)
+        }
+    }
+}
+
+impl<T> Grid2D<T>
+where
+    T: Clone,
+{
+    // every width x height sub-grid, along with its top-left corner, in reading order
+    pub fn windows(
+        &self,
+        width: usize,
+        height: usize,
+    ) -> impl Iterator<Item = (Point2D, Grid2D<T>)> + '_ {
+        let anchors = Bounds2D {
+            width: (self.bounds.width + 1).saturating_sub(width),
+            height: (self.bounds.height + 1).saturating_sub(height),
+        };
+
+        anchors.iter_horizontal().map(move |anchor| {
+            let data = self.data[anchor.y..anchor.y + height]
+                .iter()
+                .map(|row| row[anchor.x..anchor.x + width].to_vec())
+                .collect();
+            (
+                anchor,
+                Grid2D {
+                    data,
+                    bounds: Bounds2D { width, height },
+                },
+            )
+        })
+    }
+
+    // trims the grid down to the smallest rectangle holding every cell where keep is true
+    pub fn crop_to<F>(&self, keep: F) -> Option<Grid2D<T>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let (min_x, max_x, min_y, max_y) = self
+            .iter_horizontal()
+            .filter(|(_, value)| keep(value))
+            .fold(None, |acc, (pt, _)| match acc {
+                None => Some((pt.x, pt.x, pt.y, pt.y)),
+                Some((min_x, max_x, min_y, max_y)) => Some((
+                    min(min_x, pt.x),
+                    max(max_x, pt.x),
+                    min(min_y, pt.y),
+                    max(max_y, pt.y),
+                )),
+            })?;
+
+        Some(self.subgrid(Rect::new(pt(min_x, min_y), pt(max_x, max_y))))
+    }
+
+    // copy of the cells inside rect, panics if rect doesn't fit in the grid
+    pub fn subgrid(&self, rect: Rect) -> Grid2D<T> {
+        if !self.bounds.contains(&rect.terminex) {
+            panic!(
+                "rect {:?} doesn't fit in grid of {}x{}",
+                rect, self.bounds.width, self.bounds.height
+            );
+        }
+
+        let data = self.data[rect.origin.y..=rect.terminex.y]
+            .iter()
+            .map(|row| row[rect.origin.x..=rect.terminex.x].to_vec())
+            .collect();
+
+        Grid2D {
+            data,
+            bounds: Bounds2D {
+                width: rect.terminex.x - rect.origin.x + 1,
+                height: rect.terminex.y - rect.origin.y + 1,
+            },
+        }
+    }
+}
+
+impl<T> Grid2D<T>
+where
+    T: Clone,
+{
+    // one generation of a cellular automaton like Conway's life: every cell sees how many of its
+    // 8 neighbors are live and rule(current, live_count) gives its next value
+    pub fn life_step<L, F>(&self, count_live: L, rule: F) -> Grid2D<T>
+    where
+        L: Fn(&T) -> bool,
+        F: Fn(&T, usize) -> T,
+    {
+        self.map(|(pt, value)| rule(value, self.count_neighbors(pt, &count_live)))
+    }
+
+    // same as life_step, but the cells in fixed are stuck at live both going into and coming out
+    // of the step, like the broken corner lights in 2015 day 18
+    pub fn life_step_with_fixed<L, F>(
+        &self,
+        count_live: L,
+        rule: F,
+        fixed: &[Point2D],
+        live: T,
+    ) -> Grid2D<T>
+    where
+        L: Fn(&T) -> bool,
+        F: Fn(&T, usize) -> T,
+    {
+        let mut pinned = self.clone();
+        for pt in fixed {
+            pinned[*pt] = live.clone();
+        }
+        let mut next = pinned.life_step(count_live, rule);
+        for pt in fixed {
+            next[*pt] = live.clone();
+        }
+        next
+    }
+}
+
+impl<T> Grid2D<T>
+where
+    T: Clone,
+{
+    // folds the bottom half up over the top half along row `at`, which is dropped. Reflected cells
+    // are merged with combine(top, bottom). If one half is bigger, the extra rows are kept as-is
+    // at the top of the result
+    pub fn fold_horizontal<F>(&self, at: usize, combine: F) -> Grid2D<T>
+    where
+        F: Fn(&T, &T) -> T,
+    {
+        let height = max(at, self.bounds.height - at - 1);
+        let data = (0..height)
+            .map(|y| {
+                let distance = height - y;
+                let top = at.checked_sub(distance).map(|y| &self.data[y]);
+                let bottom = self.data.get(at + distance);
+                match (top, bottom) {
+                    (Some(top), Some(bottom)) => {
+                        top.iter().zip(bottom).map(|(t, b)| combine(t, b)).collect()
+                    }
+                    (Some(row), None) | (None, Some(row)) => row.clone(),
+                    (None, None) => unreachable!(),
+                }
+            })
+            .collect();
+
+        Grid2D {
+            data,
+            bounds: Bounds2D {
+                width: self.bounds.width,
+                height,
+            },
+        }
+    }
+
+    // folds the right half over the left half along column `at`, which is dropped. Reflected
+    // cells are merged with combine(left, right). If one half is bigger, the extra columns are
+    // kept as-is on the left of the result
+    pub fn fold_vertical<F>(&self, at: usize, combine: F) -> Grid2D<T>
+    where
+        F: Fn(&T, &T) -> T,
+    {
+        let width = max(at, self.bounds.width - at - 1);
+        let data = self
+            .data
+            .iter()
+            .map(|row| {
+                (0..width)
+                    .map(|x| {
+                        let distance = width - x;
+                        let left = at.checked_sub(distance).map(|x| &row[x]);
+                        let right = row.get(at + distance);
+                        match (left, right) {
+                            (Some(left), Some(right)) => combine(left, right),
+                            (Some(value), None) | (None, Some(value)) => value.clone(),
+                            (None, None) => unreachable!(),
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Grid2D {
+            data,
+            bounds: Bounds2D {
+                width,
+                height: self.bounds.height,
+            },
+        }
+    }
+}
+
+impl<T> Grid2D<T>
+where
+    T: Ord,
+{
+    // cells seen looking from `from` towards dir, stopping at (and counting) the first one at least
+    // as tall as the start
+    pub fn viewing_distance(&self, from: Point2D, dir: Direction) -> usize {
+        let height = &self[from];
+        let mut distance = 0;
+        for pt in from.ray(dir, self.bounds) {
+            distance += 1;
+            if self[pt] >= *height {
+                break;
+            }
+        }
+        distance
+    }
+
+    // true if every cell between pt and some edge is strictly shorter than pt
+    pub fn visible_from_edge(&self, pt: Point2D) -> bool {
+        let height = &self[pt];
+        CARDINAL_DIRECTIONS
+            .iter()
+            .any(|dir| pt.ray(*dir, self.bounds).all(|other| self[other] < *height))
+    }
+}
+
+impl<T> Grid2D<T>
+where
+    T: Hash + Eq,
+{
+    // how many times each distinct value appears in the grid
+    pub fn counts(&self) -> FnvHashMap<&T, usize> {
+        let mut counts = FnvHashMap::default();
+        for value in self.data.iter().flatten() {
+            *counts.entry(value).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+impl<T> Grid2D<T>
+where
+    T: PartialEq + Clone,
+{
+    // swaps every cell equal to from with to, returning how many were replaced
+    pub fn replace(&mut self, from: &T, to: T) -> usize {
+        let mut replaced = 0;
+        for cell in self.data.iter_mut().flatten() {
+            if cell == from {
+                *cell = to.clone();
+                replaced += 1;
+            }
+        }
+        replaced
+    }
+
+    pub fn contains_value(&self, value: &T) -> bool {
+        self.data.iter().flatten().any(|x| x == value)
+    }
+}
+
+impl<T> Grid2D<T>
+where
+    T: PartialEq,
+{
+    // one "x,y" line per cell equal to on, in reading order. much easier to read (and diff) than
+    // the full Display for big, mostly empty grids
+    pub fn to_sparse_string(&self, on: &T) -> String {
+        self.iter_horizontal()
+            .filter(|(_, value)| *value == on)
+            .map(|(pt, _)| pt.to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
 }
 
 impl<T> Index<Point2D> for Grid2D<T> {
@@ -688,6 +1862,66 @@ impl<T> Grid2D<T> {
     }
 }
 
+impl<T> Grid2D<T> {
+    // binary P6 image with one pixel per cell, easier to read than ascii for big grids
+    pub fn to_ppm<F>(&self, pixel: F) -> Vec<u8>
+    where
+        F: Fn(&T) -> [u8; 3],
+    {
+        let mut result =
+            format!("P6\n{} {}\n255\n", self.bounds.width, self.bounds.height).into_bytes();
+        result.reserve(self.bounds.len() * 3);
+        for row in self.data.iter() {
+            for cell in row.iter() {
+                result.extend_from_slice(&pixel(cell));
+            }
+        }
+        result
+    }
+
+    pub fn save_ppm<P, F>(&self, path: P, pixel: F) -> Result<()>
+    where
+        P: AsRef<std::path::Path>,
+        F: Fn(&T) -> [u8; 3],
+    {
+        std::fs::write(path, self.to_ppm(pixel))?;
+        Ok(())
+    }
+}
+
+impl<T> Grid2D<T> {
+    // for grids of symbols, where writing a FromStr impl isn't worth it
+    pub fn from_char_str_with<F>(input: &str, f: F) -> Grid2D<T>
+    where
+        F: Fn(char) -> T,
+    {
+        input.lines().map(|line| line.chars().map(&f)).collect()
+    }
+}
+
+impl Grid2D<char> {
+    // keeps the characters as-is, only fails when the lines aren't all the same length
+    pub fn from_chars(input: &str) -> Result<Grid2D<char>> {
+        let data: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+        if let Some(first) = data.first() {
+            if let Some((y, row)) = data
+                .iter()
+                .enumerate()
+                .find(|(_, row)| row.len() != first.len())
+            {
+                bail!(
+                    "line {} has {} characters, expected {}",
+                    y + 1,
+                    row.len(),
+                    first.len()
+                );
+            }
+        }
+
+        Ok(data.into_iter().collect())
+    }
+}
+
 impl<T> Grid2D<T>
 where
     T: FromStr,
@@ -717,21 +1951,104 @@ where
     }
 }
 
-impl<T> Hash for Grid2D<T>
+// inverse of Display for grids where every cell prints as a single character. cells that print
+// wider than that (ex: u32 values over 9) can't be split back apart, so they won't round-trip
+pub fn parse_grid<T>(s: &str) -> Result<Grid2D<T>>
 where
-    T: Hash,
+    T: FromStr,
+    <T as FromStr>::Err: std::fmt::Display,
 {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.data.hash(state);
+    let mut widths = s.lines().map(|line| line.chars().count());
+    if let Some(width) = widths.next() {
+        if let Some((row, other)) = widths.enumerate().find(|(_, w)| *w != width) {
+            bail!("row {} has {} cells, expected {}", row + 1, other, width);
+        }
     }
+
+    Grid2D::from_char_str(s)
 }
 
-impl<T> PartialEq for Grid2D<T>
+// deserializing checks that the data actually matches the bounds, since the rest of Grid2D
+// assumes it does
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for Grid2D<T>
 where
-    T: PartialEq,
+    T: serde::Deserialize<'de>,
 {
-    fn eq(&self, other: &Self) -> bool {
-        self.data == other.data
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        #[derive(serde::Deserialize)]
+        struct RawGrid2D<T> {
+            data: Vec<Vec<T>>,
+            bounds: Bounds2D,
+        }
+
+        let raw = RawGrid2D::<T>::deserialize(deserializer)?;
+        if raw.data.len() != raw.bounds.height {
+            return Err(D::Error::custom(format!(
+                "grid has {} rows, bounds expect {}",
+                raw.data.len(),
+                raw.bounds.height
+            )));
+        }
+        if let Some((y, row)) = raw
+            .data
+            .iter()
+            .enumerate()
+            .find(|(_, row)| row.len() != raw.bounds.width)
+        {
+            return Err(D::Error::custom(format!(
+                "grid row {} has {} cells, bounds expect {}",
+                y,
+                row.len(),
+                raw.bounds.width
+            )));
+        }
+
+        std::result::Result::Ok(Grid2D {
+            data: raw.data,
+            bounds: raw.bounds,
+        })
+    }
+}
+
+// grid of single digits, errors point at the line and column (both starting at 1) of the first
+// character that isn't a digit
+pub fn parse_digit_grid(s: &str) -> Result<Grid2D<u32>> {
+    s.lines()
+        .enumerate()
+        .map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(x, c)| {
+                    c.to_digit(10).ok_or_else(|| {
+                        anyhow!("invalid digit '{}' at line {}, column {}", c, y + 1, x + 1)
+                    })
+                })
+                .collect::<Result<Vec<u32>>>()
+        })
+        .collect()
+}
+
+impl<T> Hash for Grid2D<T>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data.hash(state);
+    }
+}
+
+impl<T> PartialEq for Grid2D<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
     }
 }
 
@@ -779,6 +2096,158 @@ mod tests {
         assert_eq!(points, vec![pt(1, 0), pt(0, 1)]);
     }
 
+    #[test]
+    fn direction_turns() {
+        for dir in CARDINAL_DIRECTIONS {
+            assert_eq!(dir.clockwise90().counterclockwise90(), dir);
+            assert_eq!(dir.counterclockwise90().clockwise90(), dir);
+            assert_eq!(dir.turn(0), dir);
+            assert_eq!(dir.turn(4), dir);
+            assert_eq!(dir.turn(1), dir.clockwise90());
+            assert_eq!(dir.turn(-1), dir.counterclockwise90());
+            assert_eq!(dir.turn(2), dir.opposite());
+            assert_eq!(dir.turn(-2), dir.opposite());
+            assert_eq!(dir.turn(-5), dir.counterclockwise90());
+        }
+        assert_eq!(Direction::Up.turn(3), Direction::Left);
+    }
+
+    #[test]
+    fn direction_delta() {
+        let bounds = Bounds2D {
+            width: 3,
+            height: 3,
+        };
+        for dir in CARDINAL_DIRECTIONS {
+            let (dx, dy) = dir.delta();
+            assert_eq!(pt(1, 1) + dir.delta(), pt(1, 1).mv(dir, bounds).unwrap());
+            assert_eq!(dir.opposite().delta(), (-dx, -dy));
+        }
+    }
+
+    #[test]
+    fn direction_from_char() {
+        use Direction::*;
+        for (chars, dir) in [("^Uu", Up), ("vDd", Down), ("<Ll", Left), (">Rr", Right)] {
+            for c in chars.chars() {
+                assert_eq!(Direction::from_char(c), Some(dir));
+            }
+        }
+        assert_eq!(Direction::from_char('x'), None);
+        assert_eq!(Direction::from_char('V'), None);
+    }
+
+    #[test]
+    fn direction_from_word() {
+        use Direction::*;
+        for (word, dir) in [
+            ("up", Up),
+            ("DOWN", Down),
+            ("Left", Left),
+            ("forward", Right),
+        ] {
+            assert_eq!(Direction::from_word(word), Some(dir));
+        }
+        for dir in CARDINAL_DIRECTIONS {
+            assert_eq!(Direction::from_word(&dir.to_string()), Some(dir));
+        }
+        assert_eq!(Right.to_string(), "right");
+        assert_eq!(Direction::from_word("backward"), None);
+    }
+
+    #[test]
+    fn point_move_ordinal() {
+        use Direction8::*;
+        let bounds = Bounds2D {
+            width: 3,
+            height: 3,
+        };
+        assert_eq!(pt(1, 1).move_ordinal(NorthEast, bounds), Some(pt(2, 0)));
+        assert_eq!(pt(1, 1).move_ordinal(SouthWest, bounds), Some(pt(0, 2)));
+        assert_eq!(pt(2, 0).move_ordinal(NorthEast, bounds), None);
+        assert_eq!(pt(2, 1).move_ordinal(NorthEast, bounds), None);
+        assert_eq!(pt(1, 0).move_ordinal(NorthEast, bounds), None);
+        assert_eq!(pt(0, 2).move_ordinal(SouthEast, bounds), None);
+        assert_eq!(pt(0, 0).move_ordinal(NorthWest, bounds), None);
+
+        let all: Vec<Point2D> = DIRECTIONS8
+            .iter()
+            .filter_map(|dir| pt(1, 1).move_ordinal(*dir, bounds))
+            .collect();
+        assert_eq!(all.len(), 8);
+        assert_eq!(
+            DIRECTIONS8.iter().filter(|dir| dir.is_diagonal()).count(),
+            4
+        );
+        for dir in CARDINAL_DIRECTIONS {
+            assert_eq!(Direction8::from(dir).delta(), dir.delta());
+        }
+    }
+
+    #[test]
+    fn point_translate() {
+        assert_eq!(pt(2, 3).translate(1, -2), Some(pt(3, 1)));
+        assert_eq!(pt(2, 3).translate(-3, 0), None);
+        assert_eq!(pt(2, 3).translate(0, -4), None);
+        assert_eq!(pt(2, 3) + (-2, 5), pt(0, 8));
+        assert_eq!(
+            pt(2, 3).move_by_delta(
+                1,
+                1,
+                Bounds2D {
+                    width: 3,
+                    height: 5
+                }
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn point_sub() {
+        assert_eq!(pt(5, 1) - pt(2, 4), (3, -3));
+        assert_eq!(pt(2, 4) - pt(5, 1), (-3, 3));
+        assert_eq!(pt(2, 4) + (pt(5, 1) - pt(2, 4)), pt(5, 1));
+    }
+
+    #[test]
+    fn point_line_to_horizontal() {
+        assert_eq!(
+            pt(1, 2).line_to(&pt(4, 2)),
+            vec![pt(1, 2), pt(2, 2), pt(3, 2), pt(4, 2)]
+        );
+        assert_eq!(
+            pt(4, 2).line_to(&pt(1, 2)),
+            vec![pt(4, 2), pt(3, 2), pt(2, 2), pt(1, 2)]
+        );
+        assert_eq!(pt(1, 2).line_to(&pt(1, 2)), vec![pt(1, 2)]);
+    }
+
+    #[test]
+    fn point_line_to_vertical() {
+        assert_eq!(
+            pt(3, 3).line_to(&pt(3, 0)),
+            vec![pt(3, 3), pt(3, 2), pt(3, 1), pt(3, 0)]
+        );
+    }
+
+    #[test]
+    fn point_line_to_diagonal() {
+        assert_eq!(
+            pt(0, 3).line_to(&pt(3, 0)),
+            vec![pt(0, 3), pt(1, 2), pt(2, 1), pt(3, 0)]
+        );
+        assert_eq!(
+            pt(5, 5).line_to(&pt(7, 7)),
+            vec![pt(5, 5), pt(6, 6), pt(7, 7)]
+        );
+
+        let mut forward = pt(0, 0).line_to(&pt(5, 2));
+        assert_eq!(forward.len(), 6);
+        forward.reverse();
+        assert_eq!(pt(5, 2).line_to(&pt(0, 0)), forward);
+    }
+
     #[test]
     fn bounds_iter_vertical() {
         let bounds = Bounds2D {
@@ -807,6 +2276,1048 @@ mod tests {
         );
     }
 
+    #[test]
+    fn grid_shortest_path_astar_matches_dijkstra() -> Result<()> {
+        let grid: Grid2D<u32> = Grid2D::from_char_str(
+            "\
+1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581",
+        )?;
+        assert_eq!(grid.shortest_path(), 40);
+        assert_eq!(grid.shortest_path_astar(), 40);
+
+        // random-ish weights from a small LCG
+        let mut seed: u32 = 17;
+        let grid: Grid2D<u32> = (0..12)
+            .map(|_| {
+                (0..15)
+                    .map(|_| {
+                        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                        (seed >> 16) % 9 + 1
+                    })
+                    .collect::<Vec<u32>>()
+            })
+            .collect();
+        assert_eq!(grid.shortest_path_astar(), grid.shortest_path());
+
+        Ok(())
+    }
+
+    #[test]
+    fn grid_shortest_path_between() {
+        let grid: Grid2D<u32> = vec![vec![1, 9, 1], vec![1, 1, 1], vec![9, 9, 1]]
+            .into_iter()
+            .collect();
+
+        assert_eq!(grid.shortest_path_between(pt(0, 0), pt(2, 0)), Some(4));
+        assert_eq!(grid.shortest_path_between(pt(2, 2), pt(0, 1)), Some(3));
+        assert_eq!(grid.shortest_path_between(pt(1, 1), pt(1, 1)), Some(0));
+        assert_eq!(grid.shortest_path_between(pt(0, 0), pt(3, 0)), None);
+        assert_eq!(
+            grid.shortest_path_between(Point2D::ORIGIN, grid.bounds.bottom_right()),
+            Some(grid.shortest_path())
+        );
+        assert_eq!(grid.shortest_path(), 4);
+    }
+
+    #[test]
+    fn grid_shortest_path_route() {
+        let grid: Grid2D<u32> = vec![vec![1, 9, 1, 1], vec![1, 1, 1, 9], vec![9, 9, 2, 1]]
+            .into_iter()
+            .collect();
+
+        let (cost, route) = grid.shortest_path_route(pt(0, 0), pt(3, 2)).unwrap();
+
+        assert_eq!(cost, 6);
+        assert_eq!(cost, grid.shortest_path());
+        assert_eq!(route.first(), Some(&pt(0, 0)));
+        assert_eq!(route.last(), Some(&pt(3, 2)));
+        assert_eq!(route.len(), 6);
+        assert!(route
+            .windows(2)
+            .all(|pair| pair[0].manhattan_distance(pair[1]) == 1));
+        assert_eq!(route.iter().skip(1).map(|pt| grid[pt]).sum::<u32>(), cost);
+
+        assert_eq!(
+            grid.shortest_path_route(pt(1, 1), pt(1, 1)),
+            Some((0, vec![pt(1, 1)]))
+        );
+    }
+
+    #[test]
+    fn bounds_center() {
+        let bounds = Bounds2D {
+            width: 3,
+            height: 4,
+        };
+        assert_eq!(bounds.center(), Some(pt(1, 1)));
+
+        let bounds = Bounds2D {
+            width: 1,
+            height: 1,
+        };
+        assert_eq!(bounds.center(), Some(pt(0, 0)));
+
+        for (width, height) in [(0, 0), (0, 3), (3, 0)] {
+            assert_eq!(Bounds2D { width, height }.center(), None);
+        }
+    }
+
+    #[test]
+    fn bounds_iter_border() {
+        let bounds = Bounds2D {
+            width: 3,
+            height: 3,
+        };
+        let points: Vec<Point2D> = bounds.iter_border().collect();
+        assert_eq!(
+            points,
+            vec![
+                pt(0, 0),
+                pt(1, 0),
+                pt(2, 0),
+                pt(2, 1),
+                pt(2, 2),
+                pt(1, 2),
+                pt(0, 2),
+                pt(0, 1),
+            ]
+        );
+
+        let bounds = Bounds2D {
+            width: 1,
+            height: 4,
+        };
+        let points: Vec<Point2D> = bounds.iter_border().collect();
+        assert_eq!(points, vec![pt(0, 0), pt(0, 1), pt(0, 2), pt(0, 3)]);
+
+        let bounds = Bounds2D {
+            width: 4,
+            height: 1,
+        };
+        let points: Vec<Point2D> = bounds.iter_border().collect();
+        assert_eq!(points, vec![pt(0, 0), pt(1, 0), pt(2, 0), pt(3, 0)]);
+
+        let bounds = Bounds2D {
+            width: 1,
+            height: 1,
+        };
+        let points: Vec<Point2D> = bounds.iter_border().collect();
+        assert_eq!(points, vec![pt(0, 0)]);
+
+        let bounds = Bounds2D {
+            width: 4,
+            height: 2,
+        };
+        assert_eq!(bounds.iter_border().count(), 8);
+    }
+
+    #[test]
+    fn rect_overlapping() {
+        let a = Rect::new(pt(0, 0), pt(3, 2));
+        let b = Rect::new(pt(5, 4), pt(2, 1));
+
+        assert_eq!(a.intersect(&b), Some(Rect::new(pt(2, 1), pt(3, 2))));
+        assert_eq!(b.intersect(&a), a.intersect(&b));
+        assert_eq!(a.union(&b), Rect::new(pt(0, 0), pt(5, 4)));
+        assert_eq!(a.area(), 12);
+        assert_eq!(a.intersect(&b).unwrap().area(), 4);
+    }
+
+    #[test]
+    fn rect_touching_edge() {
+        let a = Rect::new(pt(0, 0), pt(2, 2));
+        let b = Rect::new(pt(2, 0), pt(4, 2));
+
+        assert_eq!(a.intersect(&b), Some(Rect::new(pt(2, 0), pt(2, 2))));
+        assert_eq!(a.union(&b), Rect::new(pt(0, 0), pt(4, 2)));
+    }
+
+    #[test]
+    fn rect_disjoint() {
+        let a = Rect::new(pt(0, 0), pt(1, 1));
+        let b = Rect::new(pt(2, 3), pt(4, 4));
+
+        assert_eq!(a.intersect(&b), None);
+        assert_eq!(a.union(&b), Rect::new(pt(0, 0), pt(4, 4)));
+        assert_eq!(a.union(&b).area(), 25);
+    }
+
+    #[test]
+    fn rect_iter() {
+        let rect = Rect::new(pt(1, 2), pt(2, 3));
+        let points: Vec<Point2D> = rect.iter().collect();
+
+        assert_eq!(points, vec![pt(1, 2), pt(2, 2), pt(1, 3), pt(2, 3)]);
+        assert_eq!(points.len(), rect.area());
+    }
+
+    #[test]
+    fn grid_from_char_str_with() {
+        let grid = Grid2D::from_char_str_with(".#.\n##.", |c| c == '#');
+
+        assert_eq!(
+            grid.bounds,
+            Bounds2D {
+                width: 3,
+                height: 2
+            }
+        );
+        assert_eq!(
+            grid.data,
+            vec![vec![false, true, false], vec![true, true, false]]
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn grid_par_transform_matches_transform() {
+        let mut sequential: Grid2D<u32> = (0..20).map(|y| (0..30).map(move |x| x * y)).collect();
+        let mut parallel = sequential.clone();
+
+        sequential.transform(|(_, value)| value + 1);
+        parallel.par_transform(|(_, value)| value + 1);
+
+        assert_eq!(parallel, sequential);
+        assert_eq!(parallel[pt(29, 19)], 29 * 19 + 1);
+    }
+
+    #[test]
+    fn grid_windows() {
+        let grid: Grid2D<u32> = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]
+            .into_iter()
+            .collect();
+
+        let windows: Vec<(Point2D, Grid2D<u32>)> = grid.windows(2, 2).collect();
+        assert_eq!(windows.len(), 4);
+        assert_eq!(
+            windows.iter().map(|(pt, _)| *pt).collect::<Vec<_>>(),
+            vec![pt(0, 0), pt(1, 0), pt(0, 1), pt(1, 1)]
+        );
+        assert_eq!(windows[1].1.data, vec![vec![2, 3], vec![5, 6]]);
+        assert_eq!(
+            windows[1].1.bounds,
+            Bounds2D {
+                width: 2,
+                height: 2
+            }
+        );
+
+        assert_eq!(grid.windows(3, 1).count(), 3);
+        assert_eq!(grid.windows(4, 1).count(), 0);
+    }
+
+    #[test]
+    fn grid_with_border() {
+        let grid = sample_grid();
+        let bordered = grid.with_border(1, 0);
+
+        assert_eq!(
+            bordered.bounds,
+            Bounds2D {
+                width: 5,
+                height: 4
+            }
+        );
+        assert_eq!(
+            bordered.data,
+            vec![
+                vec![0, 0, 0, 0, 0],
+                vec![0, 1, 2, 3, 0],
+                vec![0, 4, 5, 6, 0],
+                vec![0, 0, 0, 0, 0],
+            ]
+        );
+        assert_eq!(bordered.crop_border(1), grid);
+        assert_eq!(grid.with_border(0, 9), grid);
+        assert_eq!(grid.with_border(3, 9).crop_border(3), grid);
+    }
+
+    #[test]
+    fn grid_grow_x() {
+        let grown = sample_grid().grow_x(2, 0);
+
+        assert_eq!(
+            grown.bounds,
+            Bounds2D {
+                width: 5,
+                height: 2
+            }
+        );
+        assert_eq!(grown.data, vec![vec![1, 2, 3, 0, 0], vec![4, 5, 6, 0, 0]]);
+        assert_eq!(sample_grid().grow_x(0, 0), sample_grid());
+    }
+
+    #[test]
+    fn grid_overlay_inside() {
+        let mut canvas = Grid2D::new_constant(
+            Bounds2D {
+                width: 4,
+                height: 3,
+            },
+            10,
+        );
+        canvas.overlay(&sample_grid(), pt(1, 1), |existing, incoming| {
+            existing + incoming
+        });
+
+        assert_eq!(
+            canvas.data,
+            vec![
+                vec![10, 10, 10, 10],
+                vec![10, 11, 12, 13],
+                vec![10, 14, 15, 16],
+            ]
+        );
+    }
+
+    #[test]
+    fn grid_overlay_clips() {
+        let mut canvas = Grid2D::new_constant(
+            Bounds2D {
+                width: 4,
+                height: 3,
+            },
+            0,
+        );
+        canvas.overlay(&sample_grid(), pt(2, 2), |_, incoming| *incoming);
+
+        assert_eq!(
+            canvas.data,
+            vec![vec![0, 0, 0, 0], vec![0, 0, 0, 0], vec![0, 0, 1, 2]]
+        );
+    }
+
+    #[test]
+    fn grid_map_mut() {
+        let mut grid = sample_grid();
+        let mut visited = vec![];
+        grid.map_mut(|pt, value| {
+            visited.push(pt);
+            *value += 1;
+        });
+
+        assert_eq!(grid.data, vec![vec![2, 3, 4], vec![5, 6, 7]]);
+        assert_eq!(visited, grid.bounds.iter_horizontal().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn grid_iter_horizontal_mut() {
+        let mut grid = sample_grid();
+        for (pt, value) in grid.iter_horizontal_mut() {
+            if pt.x % 2 == 1 {
+                *value *= 2;
+            }
+        }
+
+        assert_eq!(grid.data, vec![vec![1, 4, 3], vec![4, 10, 6]]);
+        assert_eq!(grid.iter_horizontal_mut().count(), 6);
+    }
+
+    #[test]
+    fn grid_swap() {
+        let mut grid = sample_grid();
+        grid.swap(pt(0, 0), pt(2, 1));
+        assert_eq!(grid.data, vec![vec![6, 2, 3], vec![4, 5, 1]]);
+
+        grid.swap(pt(2, 0), pt(1, 0));
+        assert_eq!(grid.data, vec![vec![6, 3, 2], vec![4, 5, 1]]);
+
+        grid.swap(pt(1, 1), pt(1, 1));
+        assert_eq!(grid.data, vec![vec![6, 3, 2], vec![4, 5, 1]]);
+    }
+
+    #[test]
+    fn grid_set_row_and_col() {
+        let mut grid = sample_grid();
+        grid.set_row(1, [7, 8, 9]);
+        assert_eq!(grid.data, vec![vec![1, 2, 3], vec![7, 8, 9]]);
+
+        grid.set_col(0, vec![0, 0]);
+        assert_eq!(grid.data, vec![vec![0, 2, 3], vec![0, 8, 9]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "row has 2 values, but grid width is 3")]
+    fn grid_set_row_wrong_length() {
+        sample_grid().set_row(0, [1, 2]);
+    }
+
+    #[test]
+    fn grid_distance_field() {
+        let grid = Grid2D::from_char_str_with(
+            "\
+.......
+.#####.
+.....#.",
+            |c| c == '.',
+        );
+
+        let distances = grid.distance_field(&[pt(0, 0), pt(6, 2)], |open| *open);
+
+        assert_eq!(distances[pt(0, 0)], Some(0));
+        assert_eq!(distances[pt(6, 2)], Some(0));
+        // the two sources meet along the top row
+        assert_eq!(distances[pt(3, 0)], Some(3));
+        assert_eq!(distances[pt(4, 0)], Some(4));
+        assert_eq!(distances[pt(5, 0)], Some(3));
+        assert_eq!(distances[pt(6, 0)], Some(2));
+        assert_eq!(distances[pt(4, 2)], Some(6));
+        assert_eq!(distances[pt(2, 1)], None);
+        assert_eq!(distances[pt(5, 2)], None);
+    }
+
+    #[test]
+    fn grid_fold_horizontal() {
+        let grid = Grid2D::from_char_str_with(
+            "\
+#..
+...
+---
+.#.
+..#",
+            |c| c == '#',
+        );
+
+        let folded = grid.fold_horizontal(2, |a, b| *a || *b);
+        assert_eq!(
+            folded.to_string_format_cell(|on| if *on { "#".into() } else { ".".into() }),
+            "#.#\n.#."
+        );
+
+        // bottom half is bigger, so its last row has nothing to merge with
+        let folded = grid.fold_horizontal(1, |a, b| *a || *b);
+        assert_eq!(
+            folded.to_string_format_cell(|on| if *on { "#".into() } else { ".".into() }),
+            "..#\n.#.\n#.."
+        );
+    }
+
+    #[test]
+    fn grid_fold_vertical() {
+        let grid = Grid2D::from_char_str_with(
+            "\
+#.|..
+..|.#
+.#|#.",
+            |c| c == '#',
+        );
+
+        let folded = grid.fold_vertical(2, |a, b| *a || *b);
+        assert_eq!(
+            folded.to_string_format_cell(|on| if *on { "#".into() } else { ".".into() }),
+            "#.\n#.\n.#"
+        );
+        assert_eq!(
+            folded.bounds,
+            Bounds2D {
+                width: 2,
+                height: 3
+            }
+        );
+    }
+
+    #[test]
+    fn grid_parse_round_trip() -> Result<()> {
+        let grid = Grid2D::from_char_str_with("0123\n4567\n8901", |c| c.to_digit(10).unwrap());
+
+        let parsed: Grid2D<u32> = parse_grid(&grid.to_string())?;
+
+        assert_eq!(parsed, grid);
+        assert_eq!(parsed.bounds, grid.bounds);
+        assert!(parse_grid::<u32>("012\n34").is_err());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn grid_serde_round_trip() -> Result<()> {
+        let grid = sample_grid();
+
+        let json = serde_json::to_string(&grid)?;
+        let parsed: Grid2D<u32> = serde_json::from_str(&json)?;
+
+        assert_eq!(parsed, grid);
+        assert_eq!(parsed.bounds, grid.bounds);
+
+        let point: Point2D = serde_json::from_str(&serde_json::to_string(&pt(4, 3))?)?;
+        assert_eq!(point, pt(4, 3));
+
+        // bounds don't match the data
+        let json = r#"{"data":[[1,2,3],[4,5,6]],"bounds":{"width":2,"height":2}}"#;
+        assert!(serde_json::from_str::<Grid2D<u32>>(json).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn grid_to_ppm() {
+        let grid = sample_grid();
+
+        let ppm = grid.to_ppm(|x| if *x % 2 == 0 { [255; 3] } else { [0; 3] });
+
+        let header = "P6\n3 2\n255\n";
+        assert!(ppm.starts_with(header.as_bytes()));
+        assert_eq!(ppm.len(), 3 * 2 * 3 + header.len());
+        assert_eq!(
+            &ppm[header.len()..header.len() + 6],
+            &[0, 0, 0, 255, 255, 255]
+        );
+    }
+
+    #[test]
+    fn grid_counts() {
+        let grid = Grid2D::from_char_str_with("#.#\n..#\n#.O", |c| c);
+
+        let counts = grid.counts();
+
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&'#'], 4);
+        assert_eq!(counts[&'.'], 4);
+        assert_eq!(counts[&'O'], 1);
+        assert_eq!(grid.count_where(|c| *c != '.'), 5);
+    }
+
+    #[test]
+    fn point_within_manhattan() {
+        let bounds = Bounds2D {
+            width: 10,
+            height: 10,
+        };
+
+        let points: Vec<Point2D> = pt(5, 5).within_manhattan(2, bounds).collect();
+        assert_eq!(points.len(), 12);
+        assert!(points.iter().all(|p| p.manhattan_distance(pt(5, 5)) <= 2));
+        assert!(!points.contains(&pt(5, 5)));
+
+        let points: Vec<Point2D> = pt(0, 0).within_manhattan(2, bounds).collect();
+        assert_eq!(
+            points,
+            vec![pt(1, 0), pt(2, 0), pt(0, 1), pt(1, 1), pt(0, 2)]
+        );
+
+        assert_eq!(pt(9, 4).within_manhattan(1, bounds).count(), 3);
+    }
+
+    #[test]
+    fn point_ray() {
+        let bounds = Bounds2D {
+            width: 5,
+            height: 3,
+        };
+
+        let points: Vec<Point2D> = pt(0, 1).ray(Direction::Right, bounds).collect();
+        assert_eq!(points, vec![pt(1, 1), pt(2, 1), pt(3, 1), pt(4, 1)]);
+
+        assert_eq!(pt(2, 2).ray(Direction::Up, bounds).count(), 2);
+        assert_eq!(pt(4, 0).ray(Direction::Right, bounds).count(), 0);
+    }
+
+    #[test]
+    fn grid_replace() {
+        let mut grid = Grid2D::from_char_str_with("S.#\n.S.\n#..", |c| c);
+
+        assert!(grid.contains_value(&'S'));
+        assert_eq!(grid.replace(&'S', '.'), 2);
+        assert!(!grid.contains_value(&'S'));
+        assert_eq!(grid.to_string(), "..#\n...\n#..");
+        assert_eq!(grid.replace(&'S', '.'), 0);
+    }
+
+    #[test]
+    fn point_chebyshev_distance() {
+        assert_eq!(pt(1, 1).chebyshev_distance(pt(4, 3)), 3);
+        assert_eq!(pt(1, 1).manhattan_distance(pt(4, 3)), 5);
+        assert_eq!(pt(4, 3).chebyshev_distance(pt(1, 1)), 3);
+        assert_eq!(pt(2, 2).chebyshev_distance(pt(2, 2)), 0);
+    }
+
+    #[test]
+    fn point_from_index_round_trip() {
+        assert_eq!(pt(3, 2).index(5), 13);
+        assert_eq!(Point2D::from_index(13, 5), pt(3, 2));
+
+        for width in 1..8 {
+            for index in 0..50 {
+                let point = Point2D::from_index(index, width);
+                assert!(point.x < width);
+                assert_eq!(point.index(width), index);
+            }
+        }
+    }
+
+    #[test]
+    fn grid_crop_to() {
+        let grid = Grid2D::from_char_str_with(".....\n..#..\n....#\n.....", |c| c == '#');
+
+        let cropped = grid.crop_to(|on| *on).unwrap();
+
+        assert_eq!(
+            cropped.bounds,
+            Bounds2D {
+                width: 3,
+                height: 2
+            }
+        );
+        assert_eq!(
+            cropped.data,
+            vec![vec![true, false, false], vec![false, false, true]]
+        );
+        assert_eq!(grid.crop_to(|_| false), None);
+    }
+
+    #[test]
+    fn grid_subgrid() {
+        let grid = Grid2D::new([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+
+        let sub = grid.subgrid(Rect::new(pt(1, 1), pt(2, 2)));
+
+        assert_eq!(sub, Grid2D::new([[5, 6], [8, 9]]));
+        assert_eq!(
+            sub.bounds,
+            Bounds2D {
+                width: 2,
+                height: 2
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_subgrid_out_of_bounds() {
+        sample_grid().subgrid(Rect::new(pt(1, 1), pt(3, 1)));
+    }
+
+    #[test]
+    fn grid_new_default() {
+        let bounds = Bounds2D {
+            width: 4,
+            height: 3,
+        };
+
+        let grid: Grid2D<u32> = Grid2D::new_default(bounds);
+
+        assert_eq!(grid.bounds, bounds);
+        assert_eq!(grid.iter_horizontal().count(), 12);
+        assert!(grid.iter_horizontal().all(|(_, x)| *x == 0));
+    }
+
+    #[test]
+    fn grid_get_signed() {
+        let grid = sample_grid();
+
+        assert_eq!(grid.get_signed(IPoint2D { x: 2, y: 1 }), Some(&6));
+        assert_eq!(grid.get_signed(IPoint2D { x: -1, y: 0 }), None);
+        assert_eq!(grid.get_signed(IPoint2D { x: 0, y: -1 }), None);
+        assert_eq!(grid.get_signed(IPoint2D { x: 3, y: 0 }), None);
+        assert_eq!(grid.get_signed(IPoint2D { x: 0, y: 2 }), None);
+    }
+
+    #[test]
+    fn grid_corner_values() {
+        assert_eq!(sample_grid().corner_values(), [&1, &3, &4, &6]);
+
+        let single = Grid2D::new([[7]]);
+        assert_eq!(single.corner_values(), [&7; 4]);
+    }
+
+    #[test]
+    fn grid_rows_mut_and_map_col() {
+        let mut grid = Grid2D::new([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+
+        if let Some(row) = grid.rows_mut().nth(1) {
+            row.fill(0);
+        }
+        grid.map_col(2, |_, x| *x = -*x);
+
+        assert_eq!(grid, Grid2D::new([[1, 2, -3], [0, 0, 0], [7, 8, -9]]));
+
+        let mut rows_seen = vec![];
+        grid.map_col(0, |y, _| rows_seen.push(y));
+        assert_eq!(rows_seen, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn grid_wrapping_cardinal_neighbors() {
+        let grid = sample_grid();
+
+        let neighbors: Vec<(Point2D, &u32)> = grid.wrapping_cardinal_neighbors(pt(0, 0)).collect();
+
+        assert_eq!(
+            neighbors,
+            vec![
+                (pt(0, 1), &4),
+                (pt(2, 0), &3),
+                (pt(1, 0), &2),
+                (pt(0, 1), &4)
+            ]
+        );
+        assert_eq!(
+            pt(2, 1).wrapping_move(Direction::Right, grid.bounds),
+            pt(0, 1)
+        );
+    }
+
+    #[test]
+    fn grid_iter_spiral() {
+        let grid = Grid2D::new([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+
+        let values: Vec<u32> = grid.iter_spiral(pt(1, 1)).map(|(_, x)| *x).collect();
+        assert_eq!(values, vec![5, 6, 9, 8, 7, 4, 1, 2, 3]);
+
+        // from a corner most of the spiral is out of bounds
+        let points: Vec<Point2D> = sample_grid()
+            .iter_spiral(pt(0, 0))
+            .map(|(p, _)| p)
+            .collect();
+        assert_eq!(
+            points,
+            vec![pt(0, 0), pt(1, 0), pt(1, 1), pt(0, 1), pt(2, 0), pt(2, 1)]
+        );
+    }
+
+    #[test]
+    fn grid_adjacent() {
+        let grid = Grid2D::new([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+
+        assert_eq!(grid.adjacent(pt(1, 1), false).count(), 4);
+        assert_eq!(grid.adjacent(pt(1, 1), true).count(), 8);
+        assert_eq!(grid.adjacent(pt(0, 0), false).count(), 2);
+        assert_eq!(grid.adjacent(pt(0, 0), true).count(), 3);
+        assert!(grid
+            .adjacent(pt(1, 1), false)
+            .eq(grid.cardinal_neighbors(pt(1, 1))));
+        assert_eq!(
+            grid.adjacent(pt(1, 1), true).map(|(_, x)| x).sum::<i32>(),
+            40
+        );
+    }
+
+    #[test]
+    fn grid_parse_digit_grid() -> Result<()> {
+        let grid = parse_digit_grid("123\n456\n")?;
+        assert_eq!(grid, sample_grid());
+
+        let err = parse_digit_grid("123\n4x6").unwrap_err();
+        assert_eq!(err.to_string(), "invalid digit 'x' at line 2, column 2");
+
+        Ok(())
+    }
+
+    #[test]
+    fn grid_from_chars() -> Result<()> {
+        let grid = Grid2D::from_chars("#..#.\n.S..#\n")?;
+
+        assert_eq!(
+            grid.bounds,
+            Bounds2D {
+                width: 5,
+                height: 2
+            }
+        );
+        assert_eq!(grid[pt(1, 1)], 'S');
+
+        let err = Grid2D::from_chars("#..\n#.\n...").unwrap_err();
+        assert_eq!(err.to_string(), "line 2 has 2 characters, expected 3");
+
+        Ok(())
+    }
+
+    #[test]
+    fn grid_diff() {
+        let grid = sample_grid();
+        let mut other = sample_grid();
+        other[pt(2, 0)] = 7;
+        other[pt(0, 1)] = 0;
+
+        assert_eq!(
+            grid.diff(&other),
+            vec![(pt(2, 0), &3, &7), (pt(0, 1), &4, &0)]
+        );
+        assert!(grid.diff(&grid).is_empty());
+    }
+
+    #[test]
+    fn transform_region_only_touches_rect() {
+        let mut grid = sample_grid();
+        grid.transform_region(Rect::new(pt(1, 0), pt(2, 1)), |(_, v)| v * 10);
+        assert_eq!(grid.data, vec![vec![1, 20, 30], vec![4, 50, 60]]);
+
+        // clipped to bounds
+        let mut grid = sample_grid();
+        grid.transform_region(Rect::new(pt(2, 1), pt(10, 10)), |(_, v)| v + 100);
+        assert_eq!(grid.data, vec![vec![1, 2, 3], vec![4, 5, 106]]);
+    }
+
+    #[test]
+    fn visibility_day8_sample() {
+        let grid = parse_digit_grid("30373\n25512\n65332\n33549\n35390\n").unwrap();
+
+        let visible = grid
+            .bounds
+            .iter_horizontal()
+            .filter(|pt| grid.visible_from_edge(*pt))
+            .count();
+        assert_eq!(visible, 21);
+        assert!(grid.visible_from_edge(pt(1, 1)));
+        assert!(!grid.visible_from_edge(pt(2, 2)));
+
+        let scenic = |at: Point2D| -> usize {
+            CARDINAL_DIRECTIONS
+                .iter()
+                .map(|dir| grid.viewing_distance(at, *dir))
+                .product()
+        };
+        assert_eq!(grid.viewing_distance(pt(2, 3), Direction::Up), 2);
+        assert_eq!(grid.viewing_distance(pt(2, 3), Direction::Left), 2);
+        assert_eq!(grid.viewing_distance(pt(2, 3), Direction::Down), 1);
+        assert_eq!(grid.viewing_distance(pt(2, 3), Direction::Right), 2);
+        assert_eq!(scenic(pt(2, 1)), 4);
+        assert_eq!(scenic(pt(2, 3)), 8);
+        assert_eq!(grid.bounds.iter_horizontal().map(scenic).max(), Some(8));
+    }
+
+    #[test]
+    fn pathfind_around_walls() {
+        let grid = Grid2D::from_chars("..#..\n..#..\n.....\n").unwrap();
+        // walls are impassable, moving down costs 2 and everything else costs 1
+        let cost = |from: Point2D, to: Point2D| match grid[to] {
+            '#' => None,
+            _ if to.y > from.y => Some(2),
+            _ => Some(1),
+        };
+
+        assert_eq!(grid.pathfind(pt(0, 0), pt(4, 0), cost), Some(10));
+        assert_eq!(grid.pathfind(pt(4, 0), pt(0, 0), cost), Some(10));
+        assert_eq!(grid.pathfind(pt(1, 1), pt(1, 1), cost), Some(0));
+        assert_eq!(grid.pathfind(pt(0, 0), pt(2, 0), cost), None);
+    }
+
+    #[test]
+    fn life_step_blinker() {
+        let conway = |alive: &bool, live: usize| matches!((alive, live), (true, 2) | (_, 3));
+        let grid = Grid2D::from_char_str_with(".....\n..#..\n..#..\n..#..\n.....\n", |c| c == '#');
+
+        let next = grid.life_step(|alive| *alive, conway);
+        assert_eq!(
+            next,
+            Grid2D::from_char_str_with(".....\n.....\n.###.\n.....\n.....\n", |c| c == '#')
+        );
+        assert_eq!(next.life_step(|alive| *alive, conway), grid);
+    }
+
+    #[test]
+    fn life_step_with_fixed_corners() {
+        let conway = |alive: &bool, live: usize| matches!((alive, live), (true, 2) | (_, 3));
+        // every cell would die of loneliness without the pinned corners
+        let mut grid = Grid2D::from_char_str_with("#...\n....\n....\n....\n", |c| c == '#');
+        let corners = grid.bounds.corners();
+
+        for _ in 0..4 {
+            grid = grid.life_step_with_fixed(|alive| *alive, conway, &corners, true);
+            assert!(corners.iter().all(|corner| grid[*corner]));
+        }
+        assert_eq!(grid.count_where(|alive| *alive), 4);
+    }
+
+    #[test]
+    fn count_neighbors_corner_edge_interior() {
+        let grid = Grid2D::new_constant(
+            Bounds2D {
+                width: 3,
+                height: 3,
+            },
+            1,
+        );
+        let any = |_: &u32| true;
+
+        assert_eq!(grid.count_neighbors(pt(0, 0), any), 3);
+        assert_eq!(grid.count_neighbors(pt(1, 0), any), 5);
+        assert_eq!(grid.count_neighbors(pt(1, 1), any), 8);
+        assert_eq!(grid.count_cardinal_neighbors(pt(0, 0), any), 2);
+        assert_eq!(grid.count_cardinal_neighbors(pt(1, 0), any), 3);
+        assert_eq!(grid.count_cardinal_neighbors(pt(1, 1), any), 4);
+
+        let grid = sample_grid();
+        assert_eq!(grid.count_neighbors(pt(1, 0), |v| v % 2 == 0), 2);
+        assert_eq!(grid.count_cardinal_neighbors(pt(1, 0), |v| v % 2 == 0), 0);
+    }
+
+    #[test]
+    fn orientations_of_tile() {
+        let tile = Grid2D::new(vec![vec![1, 2], vec![3, 4]]);
+        let orientations = tile.orientations();
+
+        assert_eq!(orientations[0], tile);
+        assert_eq!(orientations[1].data, vec![vec![3, 1], vec![4, 2]]);
+        assert_eq!(orientations[4].data, vec![vec![2, 1], vec![4, 3]]);
+        let distinct: std::collections::HashSet<_> = orientations.iter().collect();
+        assert_eq!(distinct.len(), 8);
+
+        // a symmetric tile only has one orientation
+        let plain = Grid2D::new(vec![vec![7, 7], vec![7, 7]]);
+        let distinct: std::collections::HashSet<_> = plain.orientations().into_iter().collect();
+        assert_eq!(distinct.len(), 1);
+    }
+
+    #[test]
+    fn edges_of_tile() {
+        let grid = sample_grid();
+        assert_eq!(
+            grid.edges(),
+            [vec![1, 2, 3], vec![3, 6], vec![4, 5, 6], vec![1, 4]]
+        );
+    }
+
+    #[test]
+    fn pad_to_square_grid() {
+        let padded = sample_grid().pad_to_square(0);
+        assert_eq!(
+            padded.data,
+            vec![vec![1, 2, 3], vec![4, 5, 6], vec![0, 0, 0]]
+        );
+        assert_eq!(padded.bounds.width, 3);
+        assert_eq!(padded.bounds.height, 3);
+
+        let tall = Grid2D::new(vec![vec![1], vec![2]]).pad_to_square(9);
+        assert_eq!(tall.data, vec![vec![1, 9], vec![2, 9]]);
+
+        assert_eq!(padded.pad_to_square(7), padded);
+    }
+
+    #[test]
+    fn quadrants_skip_center_lines() {
+        // the 2024 day 14 sample room
+        let bounds = Bounds2D {
+            width: 11,
+            height: 7,
+        };
+        assert_eq!(bounds.quadrant(pt(0, 0)), Some(0));
+        assert_eq!(bounds.quadrant(pt(6, 2)), Some(1));
+        assert_eq!(bounds.quadrant(pt(4, 4)), Some(2));
+        assert_eq!(bounds.quadrant(pt(10, 6)), Some(3));
+        assert_eq!(bounds.quadrant(pt(5, 0)), None);
+        assert_eq!(bounds.quadrant(pt(0, 3)), None);
+        assert_eq!(bounds.quadrant(pt(11, 0)), None);
+
+        let robots = [
+            pt(6, 0),
+            pt(6, 0),
+            pt(9, 0),
+            pt(0, 2),
+            pt(1, 3),
+            pt(2, 3),
+            pt(5, 4),
+            pt(3, 5),
+            pt(4, 5),
+            pt(4, 5),
+            pt(1, 6),
+            pt(6, 6),
+        ];
+        let counts = count_quadrants(bounds, robots);
+        assert_eq!(counts, [1, 3, 4, 1]);
+        assert_eq!(counts.iter().product::<usize>(), 12);
+
+        // even sizes split evenly with nothing left over
+        let even = Bounds2D {
+            width: 4,
+            height: 2,
+        };
+        assert_eq!(count_quadrants(even, even.iter_horizontal()), [2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn from_points_over_default() {
+        let bounds = Bounds2D {
+            width: 3,
+            height: 2,
+        };
+        let grid = Grid2D::from_points(
+            [(pt(0, 0), 1), (pt(2, 0), 3), (pt(1, 1), 5), (pt(3, 0), 9)],
+            bounds,
+            0,
+        );
+
+        assert_eq!(grid.data, vec![vec![1, 0, 3], vec![0, 5, 0]]);
+        assert_eq!(grid.bounds, bounds);
+    }
+
+    #[test]
+    fn into_cells_reading_order() {
+        let cells: Vec<(Point2D, u32)> = sample_grid().into_cells().collect();
+        assert_eq!(
+            cells,
+            vec![
+                (pt(0, 0), 1),
+                (pt(1, 0), 2),
+                (pt(2, 0), 3),
+                (pt(0, 1), 4),
+                (pt(1, 1), 5),
+                (pt(2, 1), 6),
+            ]
+        );
+
+        let grid = Grid2D::new(vec![vec!["a".to_string(), "b".to_string()]]);
+        let owned: Vec<String> = grid.into_cells().map(|(_, s)| s).collect();
+        assert_eq!(owned, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn sparse_string_lists_on_cells() {
+        let grid = Grid2D::from_char_str_with("#...\n....\n.#.#\n", |c| c == '#');
+        assert_eq!(grid.to_sparse_string(&true), "0,0\n1,2\n3,2");
+        assert_eq!(grid.to_sparse_string(&true).lines().count(), 3);
+
+        let empty = Grid2D::from_char_str_with("..\n..\n", |c| c == '#');
+        assert_eq!(empty.to_sparse_string(&true), "");
+    }
+
+    #[test]
+    fn count_enclosed_pinched_loop() {
+        // the loop dips down into the middle of the top row, so F sits under L and - under -
+        // without being connected to them. (5, 1) and (5, 2) are still inside
+        let grid = Grid2D::from_chars("F-7.F-7\n|.L-J.|\n|.F-7.|\nL-J.L-J\n").unwrap();
+        let on_loop = |_: Point2D, c: &char| *c != '.';
+        let connects_up = |_: Point2D, c: &char| matches!(c, '|' | 'L' | 'J');
+
+        assert_eq!(grid.count_enclosed(on_loop, connects_up), 4);
+
+        // nothing on the loop means nothing is inside it
+        assert_eq!(grid.count_enclosed(|_, _| false, connects_up), 0);
+    }
+
+    #[test]
+    fn count_enclosed_squeezed_pipes() {
+        // 2023 day 10 sample with the start replaced by its pipe. the gap between the two inner
+        // walls connects to the outside, leaving only 4 tiles enclosed
+        let grid = Grid2D::from_chars(
+            "..........\n\
+             .F------7.\n\
+             .|F----7|.\n\
+             .||....||.\n\
+             .||....||.\n\
+             .|L-7F-J|.\n\
+             .|..||..|.\n\
+             .L--JL--J.\n\
+             ..........\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            grid.count_enclosed(|_, c| *c != '.', |_, c| matches!(c, '|' | 'L' | 'J')),
+            4
+        );
+    }
+
     fn sample_grid() -> Grid2D<u32> {
         vec![vec![1, 2, 3], vec![4, 5, 6]].into_iter().collect()
     }
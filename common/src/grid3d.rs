@@ -0,0 +1,181 @@
+use std::ops::{Index, IndexMut};
+
+use itertools::Itertools;
+
+use crate::Point3D;
+
+// 3D counterpart to Grid2D, data is laid out as data[z][y][x]
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Bounds3D {
+    pub width: usize,
+    pub height: usize,
+    pub depth: usize,
+}
+
+impl Bounds3D {
+    pub fn iter(&self) -> impl Iterator<Item = Point3D> {
+        (0..self.depth)
+            .cartesian_product(0..self.height)
+            .cartesian_product(0..self.width)
+            .map(|((z, y), x)| Point3D { x, y, z })
+    }
+
+    pub fn len(&self) -> usize {
+        self.width * self.height * self.depth
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, pt: &Point3D) -> bool {
+        pt.x < self.width && pt.y < self.height && pt.z < self.depth
+    }
+}
+
+impl Point3D {
+    // the (up to) 6 points sharing a face with this one
+    pub fn cardinal_neighbors(&self, bounds: Bounds3D) -> impl Iterator<Item = Point3D> {
+        let x = self.x as i64;
+        let y = self.y as i64;
+        let z = self.z as i64;
+        let width = bounds.width as i64;
+        let height = bounds.height as i64;
+        let depth = bounds.depth as i64;
+
+        [
+            (-1, 0, 0),
+            (1, 0, 0),
+            (0, -1, 0),
+            (0, 1, 0),
+            (0, 0, -1),
+            (0, 0, 1),
+        ]
+        .into_iter()
+        .map(move |(dx, dy, dz)| (x + dx, y + dy, z + dz))
+        .filter(move |(x, y, z)| {
+            *x >= 0 && *x < width && *y >= 0 && *y < height && *z >= 0 && *z < depth
+        })
+        .map(|(x, y, z)| Point3D {
+            x: x as usize,
+            y: y as usize,
+            z: z as usize,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid3D<T> {
+    pub data: Vec<Vec<Vec<T>>>,
+    pub bounds: Bounds3D,
+}
+
+impl<T> Grid3D<T> {
+    pub fn iter(&self) -> impl Iterator<Item = (Point3D, &T)> {
+        self.bounds.iter().map(|pt| (pt, &self[pt]))
+    }
+
+    pub fn cardinal_neighbors(&self, pt: Point3D) -> impl Iterator<Item = (Point3D, &T)> {
+        pt.cardinal_neighbors(self.bounds).map(|pt| (pt, &self[pt]))
+    }
+
+    pub fn get(&self, pt: &Point3D) -> Option<&T> {
+        if self.bounds.contains(pt) {
+            Some(&self[*pt])
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Grid3D<T>
+where
+    T: Copy,
+{
+    pub fn new_constant(bounds: Bounds3D, value: T) -> Grid3D<T> {
+        let data = vec![vec![vec![value; bounds.width]; bounds.height]; bounds.depth];
+        Grid3D { data, bounds }
+    }
+}
+
+impl<T> Index<Point3D> for Grid3D<T> {
+    type Output = T;
+
+    fn index(&self, point: Point3D) -> &Self::Output {
+        if !self.bounds.contains(&point) {
+            panic!("index out of bounds");
+        }
+
+        &self.data[point.z][point.y][point.x]
+    }
+}
+
+impl<T> IndexMut<Point3D> for Grid3D<T> {
+    fn index_mut(&mut self, point: Point3D) -> &mut Self::Output {
+        if !self.bounds.contains(&point) {
+            panic!("index out of bounds");
+        }
+
+        &mut self.data[point.z][point.y][point.x]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pt3(x: usize, y: usize, z: usize) -> Point3D {
+        Point3D { x, y, z }
+    }
+
+    const BOUNDS: Bounds3D = Bounds3D {
+        width: 3,
+        height: 3,
+        depth: 3,
+    };
+
+    #[test]
+    fn neighbors_at_corner() {
+        let points: Vec<Point3D> = pt3(0, 0, 0).cardinal_neighbors(BOUNDS).collect();
+        assert_eq!(points, vec![pt3(1, 0, 0), pt3(0, 1, 0), pt3(0, 0, 1)]);
+
+        let points: Vec<Point3D> = pt3(2, 2, 2).cardinal_neighbors(BOUNDS).collect();
+        assert_eq!(points, vec![pt3(1, 2, 2), pt3(2, 1, 2), pt3(2, 2, 1)]);
+    }
+
+    #[test]
+    fn neighbors_in_interior() {
+        let points: Vec<Point3D> = pt3(1, 1, 1).cardinal_neighbors(BOUNDS).collect();
+        assert_eq!(
+            points,
+            vec![
+                pt3(0, 1, 1),
+                pt3(2, 1, 1),
+                pt3(1, 0, 1),
+                pt3(1, 2, 1),
+                pt3(1, 1, 0),
+                pt3(1, 1, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn grid_index_and_iter() {
+        let mut grid = Grid3D::new_constant(BOUNDS, 0);
+        grid[pt3(1, 1, 1)] = 5;
+        grid[pt3(2, 0, 1)] = 3;
+
+        assert_eq!(grid.iter().count(), 27);
+        assert_eq!(grid.iter().map(|(_, v)| v).sum::<i32>(), 8);
+        assert_eq!(
+            grid.cardinal_neighbors(pt3(1, 0, 1))
+                .map(|(_, v)| v)
+                .sum::<i32>(),
+            8
+        );
+        assert_eq!(grid.get(&pt3(3, 0, 0)), None);
+        assert_eq!(grid.iter().next(), Some((pt3(0, 0, 0), &0)));
+    }
+}
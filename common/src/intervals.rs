@@ -0,0 +1,179 @@
+// sets of integers stored as sorted, non-overlapping inclusive ranges
+use std::cmp::{max, min};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    // sorted by start, with a gap of at least one between neighbors
+    ranges: Vec<(i64, i64)>,
+}
+
+impl IntervalSet {
+    pub fn new() -> IntervalSet {
+        Default::default()
+    }
+
+    // adds lo..=hi, merging it with anything it overlaps or touches
+    pub fn insert(&mut self, lo: i64, hi: i64) {
+        let (mut lo, mut hi) = (min(lo, hi), max(lo, hi));
+
+        // everything from first to last (exclusive) overlaps or touches the new range
+        let first = self
+            .ranges
+            .partition_point(|(_, end)| end.saturating_add(1) < lo);
+        let last = self
+            .ranges
+            .partition_point(|(start, _)| *start <= hi.saturating_add(1));
+
+        if first < last {
+            lo = min(lo, self.ranges[first].0);
+            hi = max(hi, self.ranges[last - 1].1);
+        }
+        self.ranges.splice(first..last, [(lo, hi)]);
+    }
+
+    // removes lo..=hi, splitting any range that sticks out on both sides
+    pub fn subtract(&mut self, lo: i64, hi: i64) {
+        let (lo, hi) = (min(lo, hi), max(lo, hi));
+
+        // everything from first to last (exclusive) overlaps the removed range
+        let first = self.ranges.partition_point(|(_, end)| *end < lo);
+        let last = self.ranges.partition_point(|(start, _)| *start <= hi);
+        if first >= last {
+            return;
+        }
+
+        let mut remaining = Vec::with_capacity(2);
+        if self.ranges[first].0 < lo {
+            remaining.push((self.ranges[first].0, lo - 1));
+        }
+        if self.ranges[last - 1].1 > hi {
+            remaining.push((hi + 1, self.ranges[last - 1].1));
+        }
+        self.ranges.splice(first..last, remaining);
+    }
+
+    // integers in both sets
+    pub fn intersect(&self, other: &IntervalSet) -> IntervalSet {
+        let mut ranges = Vec::new();
+        let (mut a, mut b) = (0, 0);
+        while a < self.ranges.len() && b < other.ranges.len() {
+            let (a_lo, a_hi) = self.ranges[a];
+            let (b_lo, b_hi) = other.ranges[b];
+            let (lo, hi) = (max(a_lo, b_lo), min(a_hi, b_hi));
+            if lo <= hi {
+                ranges.push((lo, hi));
+            }
+            // whichever ends first can't overlap anything else in the other set
+            if a_hi < b_hi {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    // how many integers are in the set
+    pub fn total_len(&self) -> i64 {
+        self.ranges.iter().map(|(lo, hi)| hi - lo + 1).sum()
+    }
+
+    pub fn contains(&self, x: i64) -> bool {
+        let ix = self.ranges.partition_point(|(_, hi)| *hi < x);
+        matches!(self.ranges.get(ix), Some((lo, _)) if *lo <= x)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // the merged ranges, in order
+    pub fn iter(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.ranges.iter().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_of(ranges: &[(i64, i64)]) -> IntervalSet {
+        let mut set = IntervalSet::new();
+        for (lo, hi) in ranges {
+            set.insert(*lo, *hi);
+        }
+        set
+    }
+
+    #[test]
+    fn insert_overlapping() {
+        let set = set_of(&[(1, 5), (3, 8), (-2, 2)]);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![(-2, 8)]);
+        assert_eq!(set.total_len(), 11);
+
+        // swallowing several ranges at once
+        let set = set_of(&[(0, 1), (4, 5), (8, 9), (1, 8)]);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![(0, 9)]);
+    }
+
+    #[test]
+    fn insert_adjacent() {
+        let set = set_of(&[(1, 3), (4, 6), (-1, 0)]);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![(-1, 6)]);
+        assert_eq!(set.total_len(), 8);
+    }
+
+    #[test]
+    fn insert_disjoint() {
+        let set = set_of(&[(10, 12), (1, 3), (6, 6)]);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![(1, 3), (6, 6), (10, 12)]
+        );
+        assert_eq!(set.total_len(), 7);
+
+        assert!(set.contains(1));
+        assert!(set.contains(6));
+        assert!(set.contains(12));
+        assert!(!set.contains(0));
+        assert!(!set.contains(5));
+        assert!(!set.contains(13));
+        assert!(!IntervalSet::new().contains(0));
+    }
+
+    #[test]
+    fn subtract_splits_middle() {
+        let mut set = set_of(&[(0, 10), (20, 30)]);
+        set.subtract(4, 6);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![(0, 3), (7, 10), (20, 30)]
+        );
+        assert_eq!(set.total_len(), 19);
+        assert!(!set.contains(5));
+
+        // trimming the ends of two ranges and dropping one entirely
+        let mut set = set_of(&[(0, 5), (8, 9), (12, 20)]);
+        set.subtract(3, 14);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![(0, 2), (15, 20)]);
+
+        set.subtract(50, 60);
+        assert_eq!(set.total_len(), 9);
+        set.subtract(-10, 100);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn intersect_partial_overlap() {
+        let a = set_of(&[(0, 10), (20, 30), (40, 45)]);
+        let b = set_of(&[(5, 25), (28, 42)]);
+
+        let both = a.intersect(&b);
+        assert_eq!(
+            both.iter().collect::<Vec<_>>(),
+            vec![(5, 10), (20, 25), (28, 30), (40, 42)]
+        );
+        assert_eq!(both, b.intersect(&a));
+        assert!(a.intersect(&IntervalSet::new()).is_empty());
+    }
+}
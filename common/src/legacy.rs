@@ -53,6 +53,56 @@ where
     Ok(())
 }
 
+// same as run, but reads the input from stdin so it can be piped in
+pub fn run_stdin<T, U, V, FParse, F1, F2>(parse: FParse, part1: F1, part2: F2) -> Result<()>
+where
+    U: Display,
+    V: Display,
+    FParse: Fn(&str) -> Result<T>,
+    F1: Fn(&T) -> Result<U>,
+    F2: Fn(&T) -> Result<V>,
+{
+    run_from(std::io::stdin(), parse, part1, part2)
+}
+
+pub fn run_raw_stdin<U, V, F1, F2>(part1: F1, part2: F2) -> Result<()>
+where
+    U: Display,
+    V: Display,
+    F1: Fn(&str) -> Result<U>,
+    F2: Fn(&str) -> Result<V>,
+{
+    run_from(
+        std::io::stdin(),
+        |x| Ok(trim(x)),
+        |x: &String| part1(x),
+        |x: &String| part2(x),
+    )
+}
+
+fn run_from<R, T, U, V, FParse, F1, F2>(
+    reader: R,
+    parse: FParse,
+    part1: F1,
+    part2: F2,
+) -> Result<()>
+where
+    R: std::io::Read,
+    U: Display,
+    V: Display,
+    FParse: Fn(&str) -> Result<T>,
+    F1: Fn(&T) -> Result<U>,
+    F2: Fn(&T) -> Result<V>,
+{
+    let (input, parse_time) = read_from_and_parse(reader, parse)?;
+
+    let part1_time = print_and_time("Part 1", || part1(&input)).context("failure in part 1")?;
+    let part2_time = print_and_time("Part 2", || part2(&input)).context("failure in part 2")?;
+
+    print_stats(parse_time, part1_time, part2_time);
+    Ok(())
+}
+
 pub fn run_progressive<T, T2, U, V, FParse, F1, F2>(
     parse: FParse,
     part1: F1,
@@ -107,3 +157,217 @@ where
 
     Ok(())
 }
+
+// like run, but fails if an answer doesn't match the expected one. Use None for parts that don't
+// have a known answer yet
+pub fn run_verified<T, U, V, FParse, F1, F2>(
+    parse: FParse,
+    part1: F1,
+    part2: F2,
+    expected1: Option<U>,
+    expected2: Option<V>,
+) -> Result<()>
+where
+    U: Display + PartialEq,
+    V: Display + PartialEq,
+    FParse: Fn(&str) -> Result<T>,
+    F1: Fn(&T) -> Result<U>,
+    F2: Fn(&T) -> Result<V>,
+{
+    let (input, parse_time) = read_and_parse(parse)?;
+
+    let (part1_time, mismatch1) = print_and_time_and_return("Part 1", || {
+        let answer = part1(&input)?;
+        let mismatch = check_answer("Part 1", &answer, &expected1);
+        Ok((answer, mismatch))
+    })
+    .context("failure in part 1")?;
+    let (part2_time, mismatch2) = print_and_time_and_return("Part 2", || {
+        let answer = part2(&input)?;
+        let mismatch = check_answer("Part 2", &answer, &expected2);
+        Ok((answer, mismatch))
+    })
+    .context("failure in part 2")?;
+
+    print_stats(parse_time, part1_time, part2_time);
+
+    fail_on_mismatches([mismatch1, mismatch2])
+}
+
+fn check_answer<T>(description: &str, actual: &T, expected: &Option<T>) -> Option<String>
+where
+    T: Display + PartialEq,
+{
+    match expected {
+        Some(expected) if expected != actual => {
+            Some(format!("{description} - expected {expected}, got {actual}"))
+        }
+        _ => None,
+    }
+}
+
+fn fail_on_mismatches(mismatches: [Option<String>; 2]) -> Result<()> {
+    let mismatches: Vec<String> = mismatches.into_iter().flatten().collect();
+    if !mismatches.is_empty() {
+        bail!("incorrect answer\n{}", mismatches.join("\n"));
+    }
+    Ok(())
+}
+
+// Timed variants print each answer on its own line to stdout, and timings to stderr in the form
+// `part1: 4140 (1.2ms)`
+
+pub fn run_timed<T, U, V, FParse, F1, F2>(parse: FParse, part1: F1, part2: F2) -> Result<()>
+where
+    U: Display,
+    V: Display,
+    FParse: Fn(&str) -> Result<T>,
+    F1: Fn(&T) -> Result<U>,
+    F2: Fn(&T) -> Result<V>,
+{
+    let (input, parse_time) = read_and_parse(parse)?;
+    eprintln!("parse: ({})", CompactDuration(parse_time));
+
+    run_parts_timed(&input, part1, part2)?;
+    Ok(())
+}
+
+pub fn run_raw_timed<U, V, F1, F2>(part1: F1, part2: F2) -> Result<()>
+where
+    U: Display,
+    V: Display,
+    F1: Fn(&str) -> Result<U>,
+    F2: Fn(&str) -> Result<V>,
+{
+    let (input, parse_time) = read_and_parse(|x| Ok(trim(x)))?;
+    eprintln!("parse: ({})", CompactDuration(parse_time));
+
+    run_parts_timed(input.as_str(), part1, part2)?;
+    Ok(())
+}
+
+pub fn run_vec_timed<T, U, V, FParse, F1, F2>(parse: FParse, part1: F1, part2: F2) -> Result<()>
+where
+    U: Display,
+    V: Display,
+    FParse: Fn(&str) -> Result<Vec<T>>,
+    F1: Fn(&[T]) -> Result<U>,
+    F2: Fn(&[T]) -> Result<V>,
+{
+    let (input, parse_time) = read_and_parse(parse)?;
+    eprintln!("parse: ({})", CompactDuration(parse_time));
+
+    run_parts_timed(input.as_slice(), part1, part2)?;
+    Ok(())
+}
+
+fn run_parts_timed<T, U, V, F1, F2>(input: &T, part1: F1, part2: F2) -> Result<(U, V)>
+where
+    T: ?Sized,
+    U: Display,
+    V: Display,
+    F1: Fn(&T) -> Result<U>,
+    F2: Fn(&T) -> Result<V>,
+{
+    let (answer1, part1_time) = time(|| part1(input)).context("failure in part 1")?;
+    print_timed_answer("part1", &answer1, part1_time);
+    let (answer2, part2_time) = time(|| part2(input)).context("failure in part 2")?;
+    print_timed_answer("part2", &answer2, part2_time);
+
+    Ok((answer1, answer2))
+}
+
+fn print_timed_answer<T: Display>(description: &str, answer: &T, time: Duration) {
+    println!("{answer}");
+    eprintln!("{description}: {answer} ({})", CompactDuration(time));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_parts_timed_returns_answers() -> Result<()> {
+        let input: Vec<u32> = parse_lines("1\n2\n3")?;
+
+        let answers = run_parts_timed(
+            input.as_slice(),
+            |values| Ok(values.iter().sum::<u32>()),
+            |values| Ok(values.len()),
+        )?;
+        assert_eq!(answers, (6, 3));
+
+        let answers = run_parts_timed("abc", |s| Ok(s.len()), |s| Ok(s.to_uppercase()))?;
+        assert_eq!(answers, (3, "ABC".to_string()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_from_reader() -> Result<()> {
+        let answers = std::cell::RefCell::new(vec![]);
+
+        run_from(
+            &b"1\n2\n3\n"[..],
+            parse_lines::<u32>,
+            |values| {
+                let sum = values.iter().sum::<u32>();
+                answers.borrow_mut().push(sum);
+                Ok(sum)
+            },
+            |values| {
+                answers.borrow_mut().push(values.len() as u32);
+                Ok(values.len())
+            },
+        )?;
+
+        assert_eq!(*answers.borrow(), vec![6, 3]);
+
+        let (input, _) = read_from_and_parse(&b"  abc \n"[..], |x| Ok(trim(x)))?;
+        assert_eq!(input, "abc");
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_parts_timed_propagates_errors() {
+        let result = run_parts_timed("", |_| Ok(1), |_| -> Result<u32> { bail!("oops") });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn verify_wrong_answer_fails() {
+        let mismatch1 = check_answer("Part 1", &6, &Some(5));
+        let mismatch2 = check_answer("Part 2", &"abc", &None);
+        assert_eq!(mismatch1, Some("Part 1 - expected 5, got 6".to_string()));
+        assert_eq!(mismatch2, None);
+
+        let result = fail_on_mismatches([mismatch1, mismatch2]);
+        assert!(result.is_err());
+        assert!(format!("{}", result.unwrap_err()).contains("expected 5, got 6"));
+    }
+
+    #[test]
+    fn verify_right_answer_passes() {
+        let mismatch1 = check_answer("Part 1", &5, &Some(5));
+        let mismatch2 = check_answer("Part 2", &7, &None);
+
+        assert!(fail_on_mismatches([mismatch1, mismatch2]).is_ok());
+    }
+
+    #[test]
+    fn compact_duration_format() {
+        assert_eq!(
+            format!("{}", CompactDuration(Duration::from_micros(1200))),
+            "1.2ms"
+        );
+        assert_eq!(
+            format!("{}", CompactDuration(Duration::from_micros(340))),
+            "340µs"
+        );
+        assert_eq!(
+            format!("{}", CompactDuration(Duration::from_millis(2500))),
+            "2.50s"
+        );
+    }
+}
@@ -1,7 +1,7 @@
 #![feature(pattern)]
 
 use std::fmt::{self, Display};
-use std::io::{stdout, Write};
+use std::io::{stdout, Read, Write};
 use std::path::Path;
 use std::time::{Duration, Instant};
 use std::{env, fs};
@@ -37,6 +37,21 @@ pub use alg::*;
 mod three_d;
 pub use three_d::*;
 
+mod grid3d;
+pub use grid3d::*;
+
+mod text;
+pub use text::*;
+
+mod combinatorics;
+pub use combinatorics::*;
+
+mod intervals;
+pub use intervals::*;
+
+mod vm;
+pub use vm::*;
+
 pub trait Solution: std::str::FromStr
 where
     <Self as std::str::FromStr>::Err: std::fmt::Display,
@@ -123,8 +138,29 @@ where
         download_input()?
     };
 
+    parse_timed(&input, parse)
+}
+
+// like read_and_parse, but takes the whole input from a reader (ex: stdin) instead
+fn read_from_and_parse<R, T, F>(mut reader: R, parse: F) -> Result<(T, Duration)>
+where
+    R: Read,
+    F: Fn(&str) -> Result<T>,
+{
+    let mut input = String::new();
+    reader
+        .read_to_string(&mut input)
+        .context("could not read input")?;
+
+    parse_timed(&input, parse)
+}
+
+fn parse_timed<T, F>(input: &str, parse: F) -> Result<(T, Duration)>
+where
+    F: Fn(&str) -> Result<T>,
+{
     let start = Instant::now();
-    let input = parse(&input)?;
+    let input = parse(input)?;
     let parse_time = start.elapsed();
 
     Ok((input, parse_time))
@@ -169,6 +205,15 @@ where
     Ok((elapsed, more_data))
 }
 
+fn time<T, F>(runner: F) -> Result<(T, Duration)>
+where
+    F: FnOnce() -> Result<T>,
+{
+    let start = Instant::now();
+    let result = runner()?;
+    Ok((result, start.elapsed()))
+}
+
 fn print_stats(parse_time: Duration, part1_time: Duration, part2_time: Duration) {
     let term = &Term::stderr();
     term.write_line("").unwrap();
@@ -284,6 +329,22 @@ impl Display for HumanDuration {
     }
 }
 
+// short single unit version of HumanDuration, for one-line output
+struct CompactDuration(Duration);
+impl Display for CompactDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.0.as_secs_f64();
+
+        if secs >= 1.0 {
+            write!(f, "{secs:.2}s")
+        } else if self.0.as_millis() >= 1 {
+            write!(f, "{:.1}ms", secs * 1000.0)
+        } else {
+            write!(f, "{}µs", self.0.as_micros())
+        }
+    }
+}
+
 // like std::iter::once, but two items
 pub fn twice<T>(first: T, second: T) -> Twice<T> {
     Twice {
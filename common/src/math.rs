@@ -24,6 +24,72 @@ where
     greatest_common_denominator(b, a % b)
 }
 
+// returns (g, x, y) where a * x + b * y = g = gcd(a, b)
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+// binary exponentiation, intermediate values are widened so large moduli don't overflow
+pub fn mod_pow(base: u64, exp: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut exp = exp;
+    let mut result: u128 = 1;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    result as u64
+}
+
+// x where a * x % m == 1, or None when a and m aren't coprime
+pub fn mod_inv(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(i128::from(a).rem_euclid(i128::from(m)), i128::from(m));
+    if g != 1 {
+        return None;
+    }
+    x.rem_euclid(i128::from(m)).try_into().ok()
+}
+
+// Chinese remainder theorem - finds the smallest non-negative x where x % modulus == remainder
+// for every (remainder, modulus). Moduli don't need to be coprime, but if they aren't the
+// remainders have to agree, otherwise there is no solution
+pub fn crt(residues: &[(i64, i64)]) -> Option<i64> {
+    let mut result: i128 = 0;
+    let mut modulus: i128 = 1;
+
+    for &(r, m) in residues {
+        let (r, m) = (i128::from(r), i128::from(m));
+        let (g, p, _) = extended_gcd(modulus, m);
+        let diff = r - result;
+        if diff % g != 0 {
+            return None;
+        }
+
+        // result + modulus * k satisfies both, where k = diff / g * inverse(modulus / g) mod m / g
+        let step = m / g;
+        let k = (diff / g % step * p % step).rem_euclid(step);
+        result += modulus * k;
+        modulus *= step;
+        result = result.rem_euclid(modulus);
+    }
+
+    result.try_into().ok()
+}
+
 // Shoelace formula.  https://en.wikipedia.org/wiki/Shoelace_formula
 //
 // There are two implementations because most won't need 64-bit values,
@@ -52,6 +118,30 @@ pub fn shoelace_loop_area_64(vertices: Vec<IPoint2D>) -> i64 {
     area.abs() / 2
 }
 
+// twice the signed area, which is always a whole number for integer vertices. counterclockwise
+// (with y going up) is positive
+fn shoelace_doubled_area(points: &[IPoint2D]) -> i64 {
+    points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| i64::from(a.x) * i64::from(b.y) - i64::from(b.x) * i64::from(a.y))
+        .sum()
+}
+
+// signed version of shoelace_loop_area_64, without needing to own the points. loops with an odd
+// doubled area round towards zero
+pub fn shoelace_area(points: &[IPoint2D]) -> i64 {
+    shoelace_doubled_area(points) / 2
+}
+
+// Pick's theorem, A = i + b/2 - 1, solved for the number of lattice points strictly inside the
+// loop. boundary_len is the number of lattice points on the loop itself
+//
+// https://en.wikipedia.org/wiki/Pick%27s_theorem
+pub fn interior_points(points: &[IPoint2D], boundary_len: i64) -> i64 {
+    (shoelace_doubled_area(points).abs() - boundary_len) / 2 + 1
+}
+
 pub trait RangeOperations {
     type RangeOutput;
     type Index;
@@ -105,6 +195,61 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ipt;
+
+    #[test]
+    fn mod_pow_tests() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(7, 0, 13), 1);
+        assert_eq!(mod_pow(7, 5, 1), 0);
+        assert_eq!(mod_pow(u64::MAX - 1, 3, u64::MAX), u64::MAX - 1);
+    }
+
+    #[test]
+    fn mod_inv_tests() {
+        for (a, m) in [(3, 11), (10, 17), (-3, 7), (20201227, 1000000007)] {
+            let inv = mod_inv(a, m).unwrap();
+            assert!((0..m).contains(&inv));
+            assert_eq!(
+                (i128::from(a) * i128::from(inv)).rem_euclid(i128::from(m)),
+                1
+            );
+        }
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(6, 9), None);
+    }
+
+    #[test]
+    fn crt_coprime() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some(23));
+        // 2020 day 13 sample: 7,13,x,x,59,x,31,19
+        assert_eq!(
+            crt(&[(0, 7), (-1, 13), (-4, 59), (-6, 31), (-7, 19)]),
+            Some(1068781)
+        );
+        assert_eq!(crt(&[]), Some(0));
+    }
+
+    #[test]
+    fn crt_non_coprime() {
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some(10));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+    }
+
+    #[test]
+    fn shoelace_rectangle() {
+        let corners = [ipt(0, 0), ipt(4, 0), ipt(4, 3), ipt(0, 3)];
+        assert_eq!(shoelace_area(&corners), 12);
+
+        let mut clockwise = corners;
+        clockwise.reverse();
+        assert_eq!(shoelace_area(&clockwise), -12);
+
+        // a 5x4 block of points, 14 on the edge leaves a 3x2 block inside
+        assert_eq!(interior_points(&corners, 14), 6);
+        assert_eq!(interior_points(&clockwise, 14), 6);
+        assert_eq!(shoelace_loop_area_64(corners.to_vec()), 12);
+    }
 
     #[test]
     fn range_difference_tests() {
@@ -1,5 +1,7 @@
 // parsing helpers
 use anyhow::*;
+use lazy_static::lazy_static;
+use regex::Regex;
 use std::str::pattern::Pattern;
 
 pub fn trim(contents: &str) -> String {
@@ -55,6 +57,26 @@ where
         .collect()
 }
 
+// every integer in the line, in order, ignoring whatever text is around them
+pub fn extract_ints(line: &str) -> Vec<i64> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"-?\d+").unwrap();
+    }
+    RE.find_iter(line)
+        .map(|m| m.as_str().parse().unwrap())
+        .collect()
+}
+
+// same as extract_ints, but a leading - is treated as text
+pub fn extract_uints(line: &str) -> Vec<u64> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"\d+").unwrap();
+    }
+    RE.find_iter(line)
+        .map(|m| m.as_str().parse().unwrap())
+        .collect()
+}
+
 pub fn parse_chars<T>(contents: &str) -> Result<Vec<T>>
 where
     T: std::str::FromStr,
@@ -78,6 +100,39 @@ where
         .collect()
 }
 
+// paragraphs separated by blank lines, without their trailing newlines. Handles \r\n line
+// endings and extra blank lines at the end
+pub fn parse_blocks(contents: &str) -> Vec<&str> {
+    let mut blocks = vec![];
+    let mut block_start = None;
+    let mut offset = 0;
+
+    for line in contents.split_inclusive('\n') {
+        let is_blank = line.trim_end_matches(['\r', '\n']).is_empty();
+        match (is_blank, block_start) {
+            (true, Some(start)) => {
+                blocks.push(contents[start..offset].trim_end_matches(['\r', '\n']));
+                block_start = None;
+            }
+            (false, None) => block_start = Some(offset),
+            _ => (),
+        }
+        offset += line.len();
+    }
+    if let Some(start) = block_start {
+        blocks.push(contents[start..].trim_end_matches(['\r', '\n']));
+    }
+
+    blocks
+}
+
+pub fn parse_block_lines(contents: &str) -> Vec<Vec<&str>> {
+    parse_blocks(contents)
+        .into_iter()
+        .map(|block| block.lines().collect())
+        .collect()
+}
+
 pub fn parse_line_pairs<T>(contents: &str, separator: &str) -> Result<Vec<(T, T)>>
 where
     T: std::str::FromStr,
@@ -373,3 +428,43 @@ where
         self.ok_or_else(|| anyhow!("expected value, got none"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_ints_mixed() {
+        let line = "Sensor at x=2, y=-18: closest beacon is at x=-2, y=15 (id a7b42)";
+
+        assert_eq!(extract_ints(line), vec![2, -18, -2, 15, 7, 42]);
+        assert_eq!(extract_uints(line), vec![2, 18, 2, 15, 7, 42]);
+        assert_eq!(extract_ints("1-2"), vec![1, -2]);
+        assert_eq!(extract_ints("no numbers here"), Vec::<i64>::new());
+    }
+
+    #[test]
+    fn parse_blocks_paragraphs() {
+        let input = "1000\n2000\n\n4000\n\n5000\n6000\n\n";
+
+        assert_eq!(
+            parse_blocks(input),
+            vec!["1000\n2000", "4000", "5000\n6000"]
+        );
+        assert_eq!(
+            parse_block_lines(input),
+            vec![vec!["1000", "2000"], vec!["4000"], vec!["5000", "6000"]]
+        );
+    }
+
+    #[test]
+    fn parse_blocks_crlf() {
+        let input = "a\r\nb\r\n\r\nc\r\n\r\nd\r\n";
+
+        assert_eq!(parse_blocks(input), vec!["a\r\nb", "c", "d"]);
+        assert_eq!(
+            parse_block_lines(input),
+            vec![vec!["a", "b"], vec!["c"], vec!["d"]]
+        );
+    }
+}
@@ -1,7 +1,10 @@
 use std::cmp::{Eq, Ord, PartialEq};
 use std::fmt;
 use std::hash::{Hash, Hasher};
-use std::ops::{Add, Mul};
+use std::ops::{Add, Mul, Sub};
+use std::str::FromStr;
+
+use anyhow::anyhow;
 
 use crate::{Bounds2D, Direction, Point2D};
 
@@ -96,6 +99,11 @@ impl IPoint2D {
         (other.x - self.x).abs() + (other.y - self.y).abs()
     }
 
+    // unit step towards this point, ex: for following a rope knot
+    pub fn signum(&self) -> IPoint2D {
+        ipt(self.x.signum(), self.y.signum())
+    }
+
     pub fn points_within_manhattan_distance(
         &self,
         distance: i32,
@@ -113,6 +121,23 @@ impl IPoint2D {
         points.into_iter()
     }
 
+    // rotates clockwise around the origin by quarter turns. This treats y as growing downwards,
+    // like Point2D and Grid2D, so that rotating points converted from a grid looks clockwise when
+    // drawn. (1, 0) goes to (0, 1)
+    pub fn rotate_cw(&self, quarters: u32) -> IPoint2D {
+        match quarters % 4 {
+            0 => *self,
+            1 => ipt(-self.y, self.x),
+            2 => ipt(-self.x, -self.y),
+            _ => ipt(self.y, -self.x),
+        }
+    }
+
+    pub fn rotate_around(&self, pivot: IPoint2D, quarters: u32) -> IPoint2D {
+        let (dx, dy) = self.cardinal_distance(&pivot);
+        pivot + ipt(dx, dy).rotate_cw(quarters)
+    }
+
     pub fn map_infinite_to_template_bounds(&self, template_bounds: &Bounds2D) -> (Self, Self) {
         let width: i32 = template_bounds.width.try_into().unwrap();
         let height: i32 = template_bounds.height.try_into().unwrap();
@@ -170,6 +195,48 @@ impl Mul<i32> for IPoint2D {
     }
 }
 
+impl Sub for IPoint2D {
+    type Output = IPoint2D;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        IPoint2D {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl Mul<i64> for IPoint2D {
+    type Output = IPoint2D;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        let scale = |v: i32| {
+            i32::try_from(v as i64 * rhs)
+                .unwrap_or_else(|_| panic!("scaling {self} by {rhs} overflowed"))
+        };
+        IPoint2D {
+            x: scale(self.x),
+            y: scale(self.y),
+        }
+    }
+}
+
+// parses the same "x,y" format that Display writes
+impl FromStr for IPoint2D {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> anyhow::Result<IPoint2D> {
+        let (x, y) = input
+            .split_once(',')
+            .ok_or_else(|| anyhow!("expected x,y but got '{input}'"))?;
+
+        Ok(IPoint2D {
+            x: x.trim().parse()?,
+            y: y.trim().parse()?,
+        })
+    }
+}
+
 impl fmt::Display for IPoint2D {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{},{}", self.x, self.y,)
@@ -364,6 +431,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_arithmetic() {
+        let a = ipt(3, -2);
+        let b = ipt(-1, 4);
+
+        assert_eq!(a + b, ipt(2, 2));
+        assert_eq!(a - b, ipt(4, -6));
+        assert_eq!(a * 3i64, ipt(9, -6));
+        assert_eq!(b * -2i64, ipt(2, -8));
+        assert_eq!(a.manhattan_distance(b), 10);
+    }
+
+    #[test]
+    fn test_signum() {
+        assert_eq!(ipt(5, 3).signum(), ipt(1, 1));
+        assert_eq!(ipt(-5, 3).signum(), ipt(-1, 1));
+        assert_eq!(ipt(-5, -3).signum(), ipt(-1, -1));
+        assert_eq!(ipt(5, -3).signum(), ipt(1, -1));
+        assert_eq!(ipt(0, -7).signum(), ipt(0, -1));
+        assert_eq!(IPoint2D::ORIGIN.signum(), IPoint2D::ORIGIN);
+    }
+
+    #[test]
+    fn test_parse() -> anyhow::Result<()> {
+        assert_eq!("-3,14".parse::<IPoint2D>()?, ipt(-3, 14));
+        assert_eq!("7, -2".parse::<IPoint2D>()?, ipt(7, -2));
+        assert_eq!(ipt(-8, 0).to_string().parse::<IPoint2D>()?, ipt(-8, 0));
+        assert!("7".parse::<IPoint2D>().is_err());
+        assert!("a,1".parse::<IPoint2D>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rotate_cw() {
+        let point = ipt(1, 0);
+        assert_eq!(point.rotate_cw(0), ipt(1, 0));
+        assert_eq!(point.rotate_cw(1), ipt(0, 1));
+        assert_eq!(point.rotate_cw(2), ipt(-1, 0));
+        assert_eq!(point.rotate_cw(3), ipt(0, -1));
+        assert_eq!(point.rotate_cw(4), point);
+        assert_eq!(ipt(2, 3).rotate_cw(1).rotate_cw(3), ipt(2, 3));
+    }
+
+    #[test]
+    fn test_rotate_around() {
+        let pivot = ipt(5, 5);
+        assert_eq!(ipt(6, 5).rotate_around(pivot, 1), ipt(5, 6));
+        assert_eq!(ipt(6, 5).rotate_around(pivot, 2), ipt(4, 5));
+        assert_eq!(ipt(6, 5).rotate_around(pivot, 3), ipt(5, 4));
+        assert_eq!(pivot.rotate_around(pivot, 1), pivot);
+    }
+
     #[test]
     fn test_map_infinite_within_template_bounds() {
         let bounds = Bounds2D {
@@ -1,5 +1,5 @@
-use crate::{pt, Bounds2D, Point2D};
-use std::collections::HashMap;
+use crate::{ipt, pt, Bounds2D, IPoint2D, Point2D};
+use std::collections::hash_map::{self, HashMap};
 use std::ops::Index;
 
 #[derive(Debug)]
@@ -73,3 +73,111 @@ impl<T> Index<&Point2D> for SparseGrid2D<T> {
         &self.data[point]
     }
 }
+
+// like SparseGrid2D, but with signed points so it can grow in any direction
+#[derive(Debug, Clone)]
+pub struct HashGrid<T> {
+    data: HashMap<IPoint2D, T>,
+}
+
+impl<T> Default for HashGrid<T> {
+    fn default() -> Self {
+        HashGrid {
+            data: HashMap::default(),
+        }
+    }
+}
+
+impl<T> FromIterator<(IPoint2D, T)> for HashGrid<T> {
+    fn from_iter<TIter: IntoIterator<Item = (IPoint2D, T)>>(iter: TIter) -> Self {
+        HashGrid {
+            data: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> HashGrid<T> {
+    pub fn get(&self, pt: &IPoint2D) -> Option<&T> {
+        self.data.get(pt)
+    }
+
+    pub fn get_mut(&mut self, pt: &IPoint2D) -> Option<&mut T> {
+        self.data.get_mut(pt)
+    }
+
+    pub fn insert(&mut self, pt: IPoint2D, value: T) -> Option<T> {
+        self.data.insert(pt, value)
+    }
+
+    pub fn remove(&mut self, pt: &IPoint2D) -> Option<T> {
+        self.data.remove(pt)
+    }
+
+    pub fn entry(&mut self, pt: IPoint2D) -> hash_map::Entry<'_, IPoint2D, T> {
+        self.data.entry(pt)
+    }
+
+    pub fn contains(&self, pt: &IPoint2D) -> bool {
+        self.data.contains_key(pt)
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    // only the cells that have been set, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&IPoint2D, &T)> {
+        self.data.iter()
+    }
+
+    // (min, max) corners of the populated cells, inclusive
+    pub fn bounding_box(&self) -> Option<(IPoint2D, IPoint2D)> {
+        let mut points = self.data.keys();
+        let first = *points.next()?;
+
+        Some(points.fold((first, first), |(min, max), pt| {
+            (
+                ipt(min.x.min(pt.x), min.y.min(pt.y)),
+                ipt(max.x.max(pt.x), max.y.max(pt.y)),
+            )
+        }))
+    }
+}
+
+impl<T> Index<&IPoint2D> for HashGrid<T> {
+    type Output = T;
+
+    fn index(&self, point: &IPoint2D) -> &T {
+        &self.data[point]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_grid_bounding_box() {
+        let mut grid = HashGrid::default();
+        assert_eq!(grid.bounding_box(), None);
+
+        grid.insert(ipt(3, -2), 'a');
+        assert_eq!(grid.bounding_box(), Some((ipt(3, -2), ipt(3, -2))));
+
+        grid.insert(ipt(-5, 7), 'b');
+        grid.insert(ipt(0, 0), 'c');
+        grid.insert(ipt(1, -9), 'd');
+        *grid.entry(ipt(0, 0)).or_insert('z') = 'e';
+
+        assert_eq!(grid.len(), 4);
+        assert_eq!(grid[&ipt(0, 0)], 'e');
+        assert_eq!(grid.get(&ipt(2, 2)), None);
+        assert_eq!(grid.bounding_box(), Some((ipt(-5, -9), ipt(3, 7))));
+        assert_eq!(grid.iter().count(), 4);
+    }
+}
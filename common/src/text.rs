@@ -0,0 +1,86 @@
+// string scanning helpers
+use std::collections::VecDeque;
+
+// keeps the longest run of distinct chars ending at the current position, calling on_window with
+// the 1-based end position each time the run reaches `window` chars. stops early when on_window
+// returns false
+fn scan_unique_windows<F>(s: &str, window: usize, mut on_window: F)
+where
+    F: FnMut(usize) -> bool,
+{
+    if window == 0 {
+        return;
+    }
+
+    let mut buffer: VecDeque<char> = VecDeque::with_capacity(window);
+
+    for (pos, c) in s.chars().enumerate() {
+        if let Some(ix) = buffer.iter().position(|item| *item == c) {
+            // drop everything up to and including the earlier occurrence of c
+            buffer.drain(..=ix);
+        }
+        buffer.push_back(c);
+        if buffer.len() == window {
+            if !on_window(pos + 1) {
+                return;
+            }
+            // slide forward so the next position can match too
+            buffer.pop_front();
+        }
+    }
+}
+
+// 1-based end index of the first run of `window` distinct chars
+pub fn first_window_all_unique(s: &str, window: usize) -> Option<usize> {
+    let mut found = None;
+    scan_unique_windows(s, window, |pos| {
+        found = Some(pos);
+        false
+    });
+    found
+}
+
+// 1-based end index of every run of `window` distinct chars
+pub fn all_unique_windows(s: &str, window: usize) -> Vec<usize> {
+    let mut found = Vec::new();
+    scan_unique_windows(s, window, |pos| {
+        found.push(pos);
+        true
+    });
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_window_matches_day6_samples() {
+        let samples = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+
+        for (input, packet, message) in samples {
+            assert_eq!(first_window_all_unique(input, 4), Some(packet));
+            assert_eq!(first_window_all_unique(input, 14), Some(message));
+        }
+    }
+
+    #[test]
+    fn first_window_not_found() {
+        assert_eq!(first_window_all_unique("aabbcc", 3), None);
+        assert_eq!(first_window_all_unique("abc", 4), None);
+        assert_eq!(first_window_all_unique("abc", 0), None);
+    }
+
+    #[test]
+    fn all_windows() {
+        assert_eq!(all_unique_windows("abcabbd", 3), vec![3, 4, 5]);
+        assert_eq!(all_unique_windows("aaaa", 2), Vec::<usize>::new());
+        assert_eq!(all_unique_windows("abab", 1), vec![1, 2, 3, 4]);
+    }
+}
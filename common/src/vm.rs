@@ -0,0 +1,176 @@
+// shared stepping loop for the small assembly languages that show up every year. each solution
+// parses its own instruction set and implements Instruction for it
+use std::collections::{HashMap, HashSet};
+
+pub trait Instruction {
+    // run the instruction, including moving the program counter
+    fn execute(&self, machine: &mut Machine);
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Halt {
+    // the program counter left the program
+    Halted,
+    // the machine came back to a state it had already been in, with the pc it was at
+    Looping(i64),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Machine {
+    pub registers: HashMap<String, i64>,
+    pub pc: i64,
+}
+
+impl Machine {
+    pub fn new() -> Machine {
+        Default::default()
+    }
+
+    // registers that were never written read as 0
+    pub fn get(&self, register: &str) -> i64 {
+        self.registers.get(register).copied().unwrap_or(0)
+    }
+
+    pub fn set(&mut self, register: &str, value: i64) {
+        self.registers.insert(register.to_string(), value);
+    }
+
+    pub fn register_mut(&mut self, register: &str) -> &mut i64 {
+        self.registers.entry(register.to_string()).or_insert(0)
+    }
+
+    // move on to the next instruction
+    pub fn advance(&mut self) {
+        self.pc += 1;
+    }
+
+    // relative jump, like jmp/jnz
+    pub fn jump(&mut self, offset: i64) {
+        self.pc += offset;
+    }
+
+    fn current<'a, I>(&self, program: &'a [I]) -> Option<&'a I> {
+        usize::try_from(self.pc).ok().and_then(|pc| program.get(pc))
+    }
+
+    // runs until the program counter leaves the program, returning how many instructions ran
+    pub fn run_until_halt<I: Instruction>(&mut self, program: &[I]) -> usize {
+        let mut steps = 0;
+        while let Some(instruction) = self.current(program) {
+            instruction.execute(self);
+            steps += 1;
+        }
+        steps
+    }
+
+    // same as run_until_halt, but stops as soon as the pc and registers repeat, since the program
+    // will never halt from there. every state is remembered, so this is only for small programs
+    pub fn run_detect_loop<I: Instruction>(&mut self, program: &[I]) -> Halt {
+        let mut seen = HashSet::new();
+        while let Some(instruction) = self.current(program) {
+            if !seen.insert(self.snapshot()) {
+                return Halt::Looping(self.pc);
+            }
+            instruction.execute(self);
+        }
+        Halt::Halted
+    }
+
+    // registers sorted by name, skipping zeros so unset and zeroed registers look the same
+    fn snapshot(&self) -> (i64, Vec<(String, i64)>) {
+        let mut registers: Vec<(String, i64)> = self
+            .registers
+            .iter()
+            .filter(|(_, value)| **value != 0)
+            .map(|(name, value)| (name.clone(), *value))
+            .collect();
+        registers.sort();
+        (self.pc, registers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    enum Op {
+        Cpy(i64, &'static str),
+        Inc(&'static str),
+        Dec(&'static str),
+        Jnz(&'static str, i64),
+    }
+
+    impl Instruction for Op {
+        fn execute(&self, machine: &mut Machine) {
+            match self {
+                Op::Cpy(value, reg) => machine.set(reg, *value),
+                Op::Inc(reg) => *machine.register_mut(reg) += 1,
+                Op::Dec(reg) => *machine.register_mut(reg) -= 1,
+                Op::Jnz(reg, offset) => {
+                    if machine.get(reg) != 0 {
+                        machine.jump(*offset);
+                        return;
+                    }
+                }
+            }
+            machine.advance();
+        }
+    }
+
+    #[test]
+    fn increment_in_loop() {
+        // adds 5 to a by counting b down to zero
+        let program = [
+            Op::Cpy(5, "b"),
+            Op::Inc("a"),
+            Op::Dec("b"),
+            Op::Jnz("b", -2),
+        ];
+        let mut machine = Machine::new();
+
+        let steps = machine.run_until_halt(&program);
+
+        assert_eq!(machine.get("a"), 5);
+        assert_eq!(machine.get("b"), 0);
+        assert_eq!(machine.get("c"), 0);
+        assert_eq!(machine.pc, 4);
+        assert_eq!(steps, 16);
+    }
+
+    #[test]
+    fn jump_before_start_halts() {
+        let program = [Op::Inc("a"), Op::Jnz("a", -5)];
+        let mut machine = Machine::new();
+
+        assert_eq!(machine.run_until_halt(&program), 2);
+        assert_eq!(machine.pc, -4);
+    }
+
+    #[test]
+    fn detect_loop_forever() {
+        // a goes up and back down while b never changes, so the jump always happens
+        let program = [
+            Op::Cpy(1, "b"),
+            Op::Inc("a"),
+            Op::Dec("a"),
+            Op::Jnz("b", -2),
+        ];
+        let mut machine = Machine::new();
+
+        assert_eq!(machine.run_detect_loop(&program), Halt::Looping(1));
+    }
+
+    #[test]
+    fn detect_loop_halts() {
+        let program = [
+            Op::Cpy(5, "b"),
+            Op::Inc("a"),
+            Op::Dec("b"),
+            Op::Jnz("b", -2),
+        ];
+        let mut machine = Machine::new();
+
+        assert_eq!(machine.run_detect_loop(&program), Halt::Halted);
+        assert_eq!(machine.get("a"), 5);
+    }
+}